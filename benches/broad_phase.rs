@@ -0,0 +1,89 @@
+// Breitphase (Broad phase): every bullet against every asteroid, versus
+// asking the spatial grid who is nearby. Run with `cargo bench`.
+//
+// Both sides count every touching pair with the same cheap circle test, so
+// the difference is down to how many pairs get looked at at all.
+
+use asteroids::spatial::SpatialHash;
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WIDTH: f32 = 1920.0;
+const HEIGHT: f32 = 1080.0;
+const CELL: f32 = 80.0; // as in the game
+const ROUNDS: u32 = 20;
+
+struct Circle {
+    position: Vec2,
+    radius: f32,
+}
+
+fn scatter(rng: &mut StdRng, count: usize, radii: &[f32]) -> Vec<Circle> {
+    (0..count)
+        .map(|_| Circle {
+            position: Vec2::new(rng.gen_range(0.0..WIDTH), rng.gen_range(0.0..HEIGHT)),
+            radius: radii[rng.gen_range(0..radii.len())],
+        })
+        .collect()
+}
+
+fn touches(a: &Circle, b: &Circle) -> bool {
+    (a.position - b.position).length() < a.radius + b.radius
+}
+
+fn brute_force(bullets: &[Circle], asteroids: &[Circle]) -> usize {
+    bullets.iter()
+        .map(|bullet| asteroids.iter().filter(|asteroid| touches(bullet, asteroid)).count())
+        .sum()
+}
+
+fn grid(hash: &mut SpatialHash, bullets: &[Circle], asteroids: &[Circle]) -> usize {
+    hash.clear();
+    for (index, asteroid) in asteroids.iter().enumerate() {
+        hash.insert(index, asteroid.position, asteroid.radius);
+    }
+    let mut nearby = Vec::new();
+    bullets.iter()
+        .map(|bullet| {
+            hash.query(bullet.position, bullet.radius, &mut nearby);
+            nearby.iter().filter(|&&index| touches(bullet, &asteroids[index])).count()
+        })
+        .sum()
+}
+
+fn time(mut run: impl FnMut() -> usize) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut hits = 0;
+    for _ in 0..ROUNDS {
+        hits = black_box(run());
+    }
+    (start.elapsed() / ROUNDS, hits)
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut hash = SpatialHash::new(WIDTH, HEIGHT, CELL);
+
+    println!("{:>9} {:>8} {:>12} {:>12} {:>8}", "asteroids", "bullets", "brute force", "grid", "speedup");
+    for (asteroid_count, bullet_count) in [(100, 100), (500, 250), (2000, 500), (8000, 1000)] {
+        // Mostly small fragments, as after a few waves of shooting
+        let asteroids = scatter(&mut rng, asteroid_count, &[20.0, 20.0, 40.0, 80.0]);
+        let bullets = scatter(&mut rng, bullet_count, &[10.0]);
+
+        let (naive, naive_hits) = time(|| brute_force(&bullets, &asteroids));
+        let (broad, grid_hits) = time(|| grid(&mut hash, &bullets, &asteroids));
+        assert_eq!(naive_hits, grid_hits, "the grid must not lose any hits");
+
+        println!(
+            "{:>9} {:>8} {:>12.3?} {:>12.3?} {:>7.1}x",
+            asteroid_count,
+            bullet_count,
+            naive,
+            broad,
+            naive.as_secs_f64() / broad.as_secs_f64(),
+        );
+    }
+}
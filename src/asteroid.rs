@@ -0,0 +1,271 @@
+use ggez::{
+    graphics::{Canvas, Color, DrawMode, DrawParam, Mesh},
+    Context, GameResult,
+};
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::cell::OnceCell;
+use std::f32::consts::PI;
+
+use crate::collision;
+use crate::shape::{Shape, ShapeParams};
+use crate::world::{ghost_offsets, interpolate_angle, wrap_position};
+
+// Zerbrechen (Breaking up): fragments fly apart within this angle, facing
+// away from the hit, and never slower than the minimum even off a resting
+// asteroid
+const SPLIT_FAN: f32 = PI * 2.0 / 3.0;
+const MIN_SPLIT_SPEED: f32 = 50.0;
+
+pub struct Asteroid {
+    pub position: Vec2,
+    pub prev_position: Vec2,
+    pub velocity: Vec2,
+    pub shape: Shape,
+    pub rotation: f32,
+    pub prev_rotation: f32,
+    pub rotation_speed: f32,
+    pub size: AsteroidSize,
+    mesh: OnceCell<Mesh>, // the outline never changes, so it's built on the first draw and kept
+}
+
+// μέτρον ἄριστον
+#[derive(Clone, Copy)]
+pub enum AsteroidSize {
+    Large,   // Großer Asteroid
+    Medium,  // Mittlerer Asteroid
+    Small,   // Kleiner Asteroid
+}
+
+impl AsteroidSize {
+    pub fn radius(&self) -> f32 {
+        match self {
+            AsteroidSize::Large => 80.0,
+            AsteroidSize::Medium => 40.0,
+            AsteroidSize::Small => 20.0,
+        }
+    }
+
+    pub fn points(&self) -> i32 {
+        match self {
+            AsteroidSize::Large => 20,
+            AsteroidSize::Medium => 50,
+            AsteroidSize::Small => 100,
+        }
+    }
+
+    pub fn next_size(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+/// What broke an asteroid: where it was hit and the momentum behind it.
+#[derive(Clone, Copy, Debug)]
+pub struct Impact {
+    pub point: Vec2,
+    pub velocity: Vec2,
+    pub mass: f32, // same units as `Asteroid::mass`
+}
+
+/// How one size class breaks up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SplitRule {
+    pub fragments: u32,
+    pub speed: f32, // how fast the fragments fly apart, times the parent's speed
+}
+
+impl Asteroid {
+    /// Spawn at a random point on the screen edge. `speed_scale` lets later
+    /// waves come in faster than the first.
+    pub fn new_with_size(rng: &mut StdRng, width: f32, height: f32, size: AsteroidSize, speed_scale: f32) -> Self {
+        let position = if rng.gen_bool(0.5) {
+            Vec2::new(
+                if rng.gen_bool(0.5) { 0.0 } else { width },
+                rng.gen_range(0.0..height),
+            )
+        } else {
+            Vec2::new(
+                rng.gen_range(0.0..width),
+                if rng.gen_bool(0.5) { 0.0 } else { height },
+            )
+        };
+
+        let speed = rng.gen_range(50.0..150.0) * speed_scale;
+        let angle = rng.gen_range(0.0..2.0 * PI);
+        let velocity = Vec2::new(angle.cos() * speed, angle.sin() * speed);
+
+        Asteroid {
+            position,
+            prev_position: position,
+            velocity,
+            shape: Shape::generate(rng, size.radius(), &ShapeParams::default()),
+            rotation: 0.0,
+            prev_rotation: 0.0,
+            rotation_speed: rng.gen_range(-2.0..2.0),
+            size,
+            mesh: OnceCell::new(),
+        }
+    }
+
+    pub fn new(rng: &mut StdRng, width: f32, height: f32) -> Self {
+        Self::new_with_size(rng, width, height, AsteroidSize::Large, 1.0)
+    }
+
+    // σφαῖρος κυκλοτερής
+    pub fn update(&mut self, dt: f32, width: f32, height: f32) {
+        self.prev_position = self.position;
+        self.prev_rotation = self.rotation;
+        self.position += self.velocity * dt;
+        self.rotation += self.rotation_speed * dt;
+
+        // Bildschirmgrenzen Wrapping (Screen edge wrapping)
+        wrap_position(&mut self.position, &mut self.prev_position, width, height);
+    }
+
+    /// The jagged outline in world space, exactly as drawn.
+    pub fn outline(&self) -> Vec<Vec2> {
+        collision::transform(&self.shape.points, self.position, self.rotation)
+    }
+
+    pub fn bounding_radius(&self) -> f32 {
+        self.shape.bounding_radius
+    }
+
+    /// By the area of the actual outline; a small asteroid of average
+    /// build weighs about 1.
+    pub fn mass(&self) -> f32 {
+        self.shape.area / (PI * AsteroidSize::Small.radius() * AsteroidSize::Small.radius())
+    }
+
+    /// Taken as a solid disc of the same area.
+    pub fn moment_of_inertia(&self) -> f32 {
+        0.5 * self.mass() * self.shape.area / PI
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, ctx: &Context, alpha: f32, width: f32, height: f32) -> GameResult {
+        if self.mesh.get().is_none() {
+            let mesh = Mesh::new_polygon(ctx, DrawMode::stroke(2.0), &self.shape.points, Color::WHITE)?;
+            let _ = self.mesh.set(mesh);
+        }
+        let Some(mesh) = self.mesh.get() else {
+            return Ok(());
+        };
+
+        let position = self.prev_position.lerp(self.position, alpha);
+        let rotation = interpolate_angle(self.prev_rotation, self.rotation, alpha);
+        for offset in ghost_offsets(position, self.bounding_radius(), width, height) {
+            canvas.draw(mesh, DrawParam::default().dest(position + offset).rotation(rotation));
+        }
+        Ok(())
+    }
+
+    /// Break into the next size down, or None if this is as small as it
+    /// gets. The fragments carry on with the asteroid's momentum plus the
+    /// impact's and fan out away from where it was hit; how many and how
+    /// fast is down to `rule`.
+    pub fn split(&self, rng: &mut StdRng, impact: &Impact, rule: SplitRule) -> Option<Vec<Asteroid>> {
+        let next_size = self.size.next_size()?;
+        let count = rule.fragments as usize;
+
+        let away = (self.position - impact.point).try_normalize()
+            .or_else(|| impact.velocity.try_normalize())
+            .unwrap_or(Vec2::X);
+        let heading = away.y.atan2(away.x);
+        let directions: Vec<Vec2> = (0..count)
+            .map(|i| {
+                let across = if count > 1 { i as f32 / (count - 1) as f32 - 0.5 } else { 0.0 };
+                let angle = heading + across * SPLIT_FAN + rng.gen_range(-0.2..0.2);
+                Vec2::new(angle.cos(), angle.sin())
+            })
+            .collect();
+
+        let mut fragments = Vec::with_capacity(count);
+        for direction in &directions {
+            let position = self.position + *direction * next_size.radius() * 0.5;
+            let rotation = rng.gen_range(0.0..2.0 * PI);
+            fragments.push(Asteroid {
+                position,
+                prev_position: position,
+                velocity: Vec2::ZERO, // below, once all the masses are known
+                shape: Shape::generate(rng, next_size.radius(), &ShapeParams::default()),
+                rotation,
+                prev_rotation: rotation,
+                rotation_speed: rng.gen_range(-3.0..3.0),
+                size: next_size,
+                mesh: OnceCell::new(),
+            });
+        }
+
+        // Impuls (Momentum): whatever hit it is gone, and the pieces carry
+        // on with its momentum and the asteroid's. Their own outlines don't
+        // weigh the same as the whole, so it's shared by what they do weigh.
+        let masses: Vec<f32> = fragments.iter().map(Asteroid::mass).collect();
+        let total: f32 = masses.iter().sum();
+        let drift = (self.velocity * self.mass() + impact.velocity * impact.mass) / total;
+
+        // The fan only pushes the pieces apart: weighted by mass, its
+        // directions cancel out, however lopsided the jitter left it
+        let net = directions.iter().zip(&masses).map(|(direction, mass)| *direction * *mass).sum::<Vec2>() / total;
+        let spread = self.velocity.length().max(MIN_SPLIT_SPEED) * rule.speed;
+        for (fragment, direction) in fragments.iter_mut().zip(directions) {
+            fragment.velocity = drift + (direction - net) * spread;
+        }
+
+        Some(fragments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bullet::BULLET_MASS;
+    use rand::SeedableRng;
+
+    #[test]
+    fn fragments_fly_on_away_from_the_shot() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for fragments in 2..=4 {
+            for _ in 0..50 {
+                let mut asteroid = Asteroid::new_with_size(&mut rng, 1280.0, 960.0, AsteroidSize::Large, 1.0);
+                asteroid.velocity = Vec2::ZERO;
+                let impact = Impact {
+                    point: asteroid.position - Vec2::X * AsteroidSize::Large.radius(),
+                    velocity: Vec2::X * 800.0,
+                    mass: BULLET_MASS,
+                };
+                let rule = SplitRule { fragments, speed: 1.5 };
+                let pieces = asteroid.split(&mut rng, &impact, rule).unwrap();
+                let mass: f32 = pieces.iter().map(Asteroid::mass).sum();
+                let drift = pieces.iter().map(|piece| piece.velocity * piece.mass()).sum::<Vec2>() / mass;
+                assert!(drift.x > 20.0, "{} fragments: {}", fragments, drift);
+                for piece in &pieces {
+                    assert!(piece.position.x > asteroid.position.x, "{} fragments", fragments);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn splitting_conserves_momentum() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for fragments in 1..=4 {
+            for _ in 0..20 {
+                let asteroid = Asteroid::new_with_size(&mut rng, 1280.0, 960.0, AsteroidSize::Large, 1.0);
+                let impact = Impact {
+                    point: asteroid.position + Vec2::new(30.0, -50.0),
+                    velocity: Vec2::new(300.0, 700.0),
+                    mass: BULLET_MASS,
+                };
+                let before = asteroid.velocity * asteroid.mass() + impact.velocity * impact.mass;
+                let pieces = asteroid.split(&mut rng, &impact, SplitRule { fragments, speed: 1.5 }).unwrap();
+                let after = pieces.iter().map(|piece| piece.velocity * piece.mass()).sum::<Vec2>();
+                assert!((after - before).length() < 1e-3 * before.length(), "{} fragments: {} vs {}", fragments, after, before);
+            }
+        }
+    }
+}
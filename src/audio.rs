@@ -0,0 +1,299 @@
+// Klang (Sound): every effect is synthesized at startup, no sound files
+// ἁρμονία
+//
+// `synthesize` and `wav` are plain arithmetic on sample buffers, so they
+// run anywhere; only `Audio`, which hands the results to ggez, needs a
+// sound device. The world never plays anything itself, it just leaves a
+// `Sound` in `World::sounds` for the shell to pick up.
+
+use ggez::{
+    audio::{SoundData, SoundSource, Source},
+    Context, GameResult,
+};
+use std::f32::consts::PI;
+
+use crate::asteroid::AsteroidSize;
+
+pub const SAMPLE_RATE: u32 = 22_050;
+
+// Herzschlag (Heartbeat): slow with a full field, quicker as it empties
+const BEAT_FASTEST: f32 = 0.25; // seconds between beats
+const BEAT_SLOWEST: f32 = 1.0;
+const BEAT_SLOWDOWN_PER_ASTEROID: f32 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sound {
+    Fire,
+    BangLarge,
+    BangMedium,
+    BangSmall,
+    Thrust, // loops while the engine runs
+    ShipExplosion,
+    BeatLow,
+    BeatHigh,
+}
+
+impl Sound {
+    /// In declaration order, so `sound as usize` is a sound's place here.
+    pub const ALL: [Sound; 8] = [
+        Sound::Fire,
+        Sound::BangLarge,
+        Sound::BangMedium,
+        Sound::BangSmall,
+        Sound::Thrust,
+        Sound::ShipExplosion,
+        Sound::BeatLow,
+        Sound::BeatHigh,
+    ];
+
+    /// The explosion for an asteroid of `size`.
+    pub fn bang(size: AsteroidSize) -> Sound {
+        match size {
+            AsteroidSize::Large => Sound::BangLarge,
+            AsteroidSize::Medium => Sound::BangMedium,
+            AsteroidSize::Small => Sound::BangSmall,
+        }
+    }
+}
+
+/// Mono samples between -1 and 1 at `SAMPLE_RATE`. The same every time:
+/// the noise comes from a fixed seed.
+pub fn synthesize(sound: Sound) -> Vec<f32> {
+    match sound {
+        Sound::Fire => sweep(0.15, 1400.0, 300.0, 0.5),
+        Sound::BangLarge => noise(0.9, 0.04, 4.0, 0.9),
+        Sound::BangMedium => noise(0.6, 0.08, 6.0, 0.8),
+        Sound::BangSmall => noise(0.35, 0.16, 10.0, 0.7),
+        Sound::Thrust => seamless(noise(0.55, 0.05, 0.0, 0.4), 0.05),
+        Sound::ShipExplosion => normalized(mix(&noise(1.5, 0.06, 2.5, 0.8), &sweep(1.0, 180.0, 40.0, 0.3)), 0.9),
+        Sound::BeatLow => thump(0.12, 98.0),
+        Sound::BeatHigh => thump(0.12, 110.0),
+    }
+}
+
+/// Seconds from one heartbeat to the next with `remaining` asteroids left.
+pub fn beat_interval(remaining: usize) -> f32 {
+    (BEAT_FASTEST + remaining as f32 * BEAT_SLOWDOWN_PER_ASTEROID).min(BEAT_SLOWEST)
+}
+
+/// 16-bit mono PCM in a RIFF container, as `SoundData` wants it.
+pub fn wav(samples: &[f32]) -> Vec<u8> {
+    let data_size = samples.len() as u32 * 2;
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // format chunk size
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // channels
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // bytes per second
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // bytes per frame
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+fn sample_count(seconds: f32) -> usize {
+    (seconds * SAMPLE_RATE as f32) as usize
+}
+
+/// Pew: a square wave gliding from `from` to `to` Hz, dying away.
+fn sweep(seconds: f32, from: f32, to: f32, peak: f32) -> Vec<f32> {
+    let count = sample_count(seconds);
+    let mut phase = 0.0;
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / count as f32;
+            phase = (phase + (from + (to - from) * t) / SAMPLE_RATE as f32).fract();
+            let square = if phase < 0.5 { 1.0 } else { -1.0 };
+            square * peak * (1.0 - t) * (1.0 - t)
+        })
+        .collect()
+}
+
+/// Rumble: white noise through a one-pole low pass (`smoothing` near zero
+/// is darker), fading by `decay` per second, scaled up to `peak`.
+fn noise(seconds: f32, smoothing: f32, decay: f32, peak: f32) -> Vec<f32> {
+    let mut state: u32 = 0x2545_f491;
+    let mut filtered = 0.0;
+    let samples: Vec<f32> = (0..sample_count(seconds))
+        .map(|i| {
+            // xorshift: good enough for noise, and no rng to thread through
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let white = state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+            filtered += smoothing * (white - filtered);
+            filtered * (-decay * i as f32 / SAMPLE_RATE as f32).exp()
+        })
+        .collect();
+    normalized(samples, peak)
+}
+
+/// Thump: a low sine that fades out quickly.
+fn thump(seconds: f32, frequency: f32) -> Vec<f32> {
+    let count = sample_count(seconds);
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            (2.0 * PI * frequency * t).sin() * 0.8 * (1.0 - i as f32 / count as f32)
+        })
+        .collect()
+}
+
+fn mix(a: &[f32], b: &[f32]) -> Vec<f32> {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0.0) + b.get(i).unwrap_or(&0.0))
+        .collect()
+}
+
+fn normalized(mut samples: Vec<f32>, peak: f32) -> Vec<f32> {
+    let loudest = samples.iter().fold(0.0, |loudest: f32, sample| loudest.max(sample.abs()));
+    if loudest > 0.0 {
+        for sample in &mut samples {
+            *sample *= peak / loudest;
+        }
+    }
+    samples
+}
+
+/// Blend the last `seconds` into the start so the buffer loops without
+/// a click, and drop them from the end.
+fn seamless(mut samples: Vec<f32>, seconds: f32) -> Vec<f32> {
+    let overlap = sample_count(seconds).min(samples.len() / 2);
+    let tail = samples.len() - overlap;
+    for i in 0..overlap {
+        let t = i as f32 / overlap as f32;
+        samples[i] = samples[i] * t + samples[tail + i] * (1.0 - t);
+    }
+    samples.truncate(tail);
+    samples
+}
+
+/// The synthesized sounds loaded into ggez, plus the heartbeat's timing.
+pub struct Audio {
+    sources: Vec<Source>, // in `Sound::ALL` order
+    volume: f32,
+    muted: bool,
+    beat_timer: f32,
+    high_beat: bool, // the next beat is the higher tone
+}
+
+impl Audio {
+    pub fn new(ctx: &Context, volume: f32, muted: bool) -> GameResult<Audio> {
+        let mut sources = Vec::with_capacity(Sound::ALL.len());
+        for sound in Sound::ALL {
+            let data = SoundData::from_bytes(&wav(&synthesize(sound)));
+            let mut source = Source::from_data(ctx, data)?;
+            source.set_repeat(sound == Sound::Thrust);
+            sources.push(source);
+        }
+        Ok(Audio {
+            sources,
+            volume,
+            muted,
+            beat_timer: 0.0,
+            high_beat: false,
+        })
+    }
+
+    /// `volume` from 0 to 1; `muted` silences everything, including a
+    /// thrust rumble already going.
+    pub fn set_levels(&mut self, volume: f32, muted: bool) {
+        self.volume = volume;
+        self.muted = muted;
+        let gain = self.gain();
+        self.sources[Sound::Thrust as usize].set_volume(gain);
+    }
+
+    fn gain(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.volume
+        }
+    }
+
+    /// Start `sound` on top of whatever is already playing.
+    pub fn play(&mut self, ctx: &Context, sound: Sound) -> GameResult {
+        if self.muted {
+            return Ok(());
+        }
+        let gain = self.gain();
+        let source = &mut self.sources[sound as usize];
+        // A detached sound keeps the volume of the sink it started on
+        source.set_volume(gain);
+        source.play_detached(ctx)
+    }
+
+    /// Keep the engine rumbling for as long as `on`.
+    pub fn thrust(&mut self, ctx: &Context, on: bool) -> GameResult {
+        let gain = self.gain();
+        let source = &mut self.sources[Sound::Thrust as usize];
+        if on && !source.playing() {
+            source.set_volume(gain);
+            source.play(ctx)?;
+        } else if !on && source.playing() {
+            source.stop(ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Count down to the next beat, alternating low and high, and the
+    /// fewer `remaining` asteroids the quicker.
+    pub fn heartbeat(&mut self, ctx: &Context, dt: f32, remaining: usize) -> GameResult {
+        self.beat_timer -= dt;
+        if self.beat_timer > 0.0 {
+            return Ok(());
+        }
+        self.beat_timer = beat_interval(remaining);
+        let sound = if self.high_beat { Sound::BeatHigh } else { Sound::BeatLow };
+        self.high_beat = !self.high_beat;
+        self.play(ctx, sound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_sound_has_samples_in_range() {
+        for sound in Sound::ALL {
+            let samples = synthesize(sound);
+            assert!(!samples.is_empty(), "{:?}", sound);
+            assert!(samples.iter().all(|sample| sample.abs() <= 1.0), "{:?}", sound);
+            assert_eq!(samples, synthesize(sound), "{:?}", sound);
+        }
+    }
+
+    #[test]
+    fn wav_header_matches_the_data() {
+        let samples = [0.0, 0.5, -0.5, 1.0, -1.0];
+        let bytes = wav(&samples);
+        let word = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        assert_eq!(bytes.len(), 44 + samples.len() * 2);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(word(4) as usize, bytes.len() - 8);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(word(24), SAMPLE_RATE);
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(word(40) as usize, samples.len() * 2);
+        assert_eq!(i16::from_le_bytes([bytes[50], bytes[51]]), i16::MAX);
+    }
+
+    #[test]
+    fn heartbeat_quickens_as_the_field_empties() {
+        let intervals: Vec<f32> = (0..30).map(beat_interval).collect();
+        assert!(intervals.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!(beat_interval(1) < beat_interval(8));
+        assert_eq!(beat_interval(0), BEAT_FASTEST);
+        assert_eq!(beat_interval(100), BEAT_SLOWEST);
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Tastenbelegung (Key bindings): which keys do what, in a plain text file.
+ * One line per action, its name and then any number of keys; '#' starts a
+ * comment. Actions left out keep their default keys.
+ *
+ *   # asteroids bindings
+ *   RotateLeft Left A
+ *   Thrust Up W
+ *   Fire Space LControl
+ *
+ * A key does one thing only: binding it to an action takes it away from
+ * whatever had it before. Menus always use the arrows, Return and Escape,
+ * and Escape always pauses, so a bad binding can't lock anyone out.
+ */
+
+use ggez::input::keyboard::KeyCode;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::input::Action;
+
+const HEADER: &str = "# asteroids bindings";
+
+/// The keys that can be bound, by their names in the file. Escape is
+/// kept back for pausing and backing out of menus.
+const KEYS: &[KeyCode] = &[
+    KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+    KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M, KeyCode::N,
+    KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T, KeyCode::U,
+    KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+    KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
+    KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+    KeyCode::Left, KeyCode::Right, KeyCode::Up, KeyCode::Down,
+    KeyCode::Space, KeyCode::Return, KeyCode::Tab, KeyCode::Back,
+    KeyCode::Insert, KeyCode::Delete, KeyCode::Home, KeyCode::End, KeyCode::PageUp, KeyCode::PageDown,
+    KeyCode::LShift, KeyCode::RShift, KeyCode::LControl, KeyCode::RControl, KeyCode::LAlt, KeyCode::RAlt,
+    KeyCode::Numpad0, KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4,
+    KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8, KeyCode::Numpad9,
+    KeyCode::NumpadAdd, KeyCode::NumpadSubtract, KeyCode::NumpadMultiply, KeyCode::NumpadDivide,
+    KeyCode::NumpadDecimal, KeyCode::NumpadEnter,
+    KeyCode::Apostrophe, KeyCode::Backslash, KeyCode::Comma, KeyCode::Equals, KeyCode::Grave,
+    KeyCode::Minus, KeyCode::Period, KeyCode::Semicolon, KeyCode::Slash,
+    KeyCode::LBracket, KeyCode::RBracket,
+];
+
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+/// None for names that aren't in `KEYS`.
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEYS.iter().copied().find(|key| key_name(*key) == name)
+}
+
+/// Whether `key` can be bound, i.e. written to and read from the file.
+pub fn bindable(key: KeyCode) -> bool {
+    KEYS.contains(&key)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bindings {
+    keys: Vec<(Action, Vec<KeyCode>)>, // in `Action::ALL` order
+}
+
+impl Default for Bindings {
+    /// Arrows as in the arcade, WASD for the other hand.
+    fn default() -> Self {
+        let keys = |action| match action {
+            Action::RotateLeft => vec![KeyCode::Left, KeyCode::A],
+            Action::RotateRight => vec![KeyCode::Right, KeyCode::D],
+            Action::Thrust => vec![KeyCode::Up, KeyCode::W],
+            Action::Fire => vec![KeyCode::Space],
+            Action::Hyperspace => vec![KeyCode::Down, KeyCode::S],
+            Action::Pause => vec![KeyCode::P],
+            Action::Restart => vec![KeyCode::R],
+        };
+        Bindings {
+            keys: Action::ALL.into_iter().map(|action| (action, keys(action))).collect(),
+        }
+    }
+}
+
+impl Bindings {
+    /// No file yet just means the defaults.
+    pub fn load(path: &Path) -> io::Result<Bindings> {
+        match fs::read_to_string(path) {
+            Ok(text) => Bindings::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Bindings::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    pub fn parse(text: &str) -> io::Result<Bindings> {
+        let mut bindings = Bindings::default();
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let bad = || io::Error::new(io::ErrorKind::InvalidData, format!("bad bindings line: {}", line));
+            let mut words = line.split_whitespace();
+            let action = words.next().and_then(Action::from_name).ok_or_else(bad)?;
+            bindings.clear(action);
+            for word in words {
+                bindings.bind(action, key_from_name(word).ok_or_else(bad)?);
+            }
+        }
+        Ok(bindings)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (action, keys) in &self.keys {
+            text.push_str(action.name());
+            for key in keys {
+                text.push(' ');
+                text.push_str(&key_name(*key));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn action_for(&self, key: KeyCode) -> Option<Action> {
+        self.keys.iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keys.iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys)
+    }
+
+    /// Add `key` to the keys for `action`, taking it off any other action.
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        for (bound, keys) in &mut self.keys {
+            if *bound == action {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            } else {
+                keys.retain(|other| *other != key);
+            }
+        }
+    }
+
+    /// Leave `action` without any keys.
+    pub fn clear(&mut self, action: Action) {
+        for (bound, keys) in &mut self.keys {
+            if *bound == action {
+                keys.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_replace_an_actions_keys() {
+        let bindings = Bindings::parse("# asteroids bindings\nFire Space LControl # both\nThrust\n").unwrap();
+        assert_eq!(bindings.keys(Action::Fire), &[KeyCode::Space, KeyCode::LControl]);
+        assert!(bindings.keys(Action::Thrust).is_empty());
+        assert_eq!(bindings.keys(Action::Pause), &[KeyCode::P]);
+    }
+
+    #[test]
+    fn a_key_does_one_thing_only() {
+        let bindings = Bindings::parse("Fire A\n").unwrap();
+        assert_eq!(bindings.action_for(KeyCode::A), Some(Action::Fire));
+        assert_eq!(bindings.keys(Action::RotateLeft), &[KeyCode::Left]);
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut bindings = Bindings::default();
+        bindings.bind(Action::Hyperspace, KeyCode::NumpadEnter);
+        bindings.clear(Action::Restart);
+        assert_eq!(Bindings::parse(&bindings.to_text()).unwrap(), bindings);
+    }
+
+    #[test]
+    fn rejects_unknown_actions_and_keys() {
+        assert!(Bindings::parse("Jump Space\n").is_err());
+        assert!(Bindings::parse("Fire Escape\n").is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use ggez::{
+    graphics::{Color, DrawMode, DrawParam, Mesh},
+    Context, GameResult,
+};
+use glam::Vec2;
+
+use crate::render::Batch;
+use crate::world::{ghost_offsets, wrap_position};
+
+const RADIUS: f32 = 4.0;
+
+/// For knocking asteroid fragments about, in `Asteroid::mass` units. More
+/// than a bullet's share by size, so a hit visibly shoves the pieces: about
+/// 50 px/s on a large asteroid's two, 200 on a medium's.
+pub const BULLET_MASS: f32 = 0.5;
+
+pub struct Bullet {
+    pub position: Vec2,
+    pub prev_position: Vec2,
+    pub velocity: Vec2,
+    pub lifetime: f32,
+}
+
+impl Bullet {
+    pub fn update(&mut self, dt: f32, width: f32, height: f32) {
+        self.prev_position = self.position;
+        self.position += self.velocity * dt;
+        self.lifetime -= dt;
+
+        // Wrap around screen
+        wrap_position(&mut self.position, &mut self.prev_position, width, height);
+    }
+
+    /// The one mesh every bullet is drawn with, for a `Batch`.
+    pub fn mesh(ctx: &Context) -> GameResult<Mesh> {
+        Mesh::new_circle(ctx, DrawMode::fill(), Vec2::ZERO, RADIUS, 0.1, Color::WHITE)
+    }
+
+    pub fn draw(&self, batch: &mut Batch, alpha: f32, width: f32, height: f32) {
+        let position = self.prev_position.lerp(self.position, alpha);
+        for offset in ghost_offsets(position, RADIUS, width, height) {
+            batch.push(DrawParam::default().dest(position + offset));
+        }
+    }
+}
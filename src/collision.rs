@@ -0,0 +1,252 @@
+// Kollisionsgeometrie (Collision geometry)
+// γεωμετρία: hit what the player sees, not a circle around it.
+//
+// Asteroid outlines are jagged and concave, so this sticks to tests that
+// work for any simple polygon: point-in-polygon by ray crossing and
+// edge-against-edge intersection.
+//
+// The arena is a torus. Rather than testing every pair four times, callers
+// move one side over to its image nearest the other (see `wrapped_delta`)
+// and then test as usual.
+
+use glam::Vec2;
+
+/// Rotate local outline points and move them to `position`.
+pub fn transform(points: &[Vec2], position: Vec2, rotation: f32) -> Vec<Vec2> {
+    let (sin, cos) = rotation.sin_cos();
+    points
+        .iter()
+        .map(|point| {
+            Vec2::new(
+                point.x * cos - point.y * sin + position.x,
+                point.x * sin + point.y * cos + position.y,
+            )
+        })
+        .collect()
+}
+
+/// Distance from the local origin to the farthest outline point.
+pub fn bounding_radius(points: &[Vec2]) -> f32 {
+    points.iter().map(|point| point.length()).fold(0.0, f32::max)
+}
+
+/// Even-odd rule, so concave outlines are fine.
+pub fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Shortest signed distance along one wrapping axis of length `span`.
+/// A `span` of zero stands for an axis that doesn't wrap.
+pub fn wrapped(delta: f32, span: f32) -> f32 {
+    if span > 0.0 {
+        delta - span * (delta / span).round()
+    } else {
+        delta
+    }
+}
+
+/// Shortest offset from `from` to `to` when leaving one edge means coming
+/// back in at the opposite one.
+pub fn wrapped_delta(from: Vec2, to: Vec2, width: f32, height: f32) -> Vec2 {
+    let delta = to - from;
+    Vec2::new(wrapped(delta.x, width), wrapped(delta.y, height))
+}
+
+/// The point on segment `a`-`b` nearest to `point`.
+pub fn closest_point_on_segment(point: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let length = ab.length_squared();
+    if length < f32::EPSILON {
+        return a;
+    }
+    let t = ((point - a).dot(ab) / length).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// Closest distance from `point` to the segment `a`-`b`.
+pub fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    (point - closest_point_on_segment(point, a, b)).length()
+}
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Whether segment `a1`-`a2` crosses or touches segment `b1`-`b2`.
+pub fn segments_intersect(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> bool {
+    let r = a2 - a1;
+    let s = b2 - b1;
+    let denominator = cross(r, s);
+    let offset = b1 - a1;
+
+    if denominator.abs() < f32::EPSILON {
+        // Parallel: only collinear overlap counts
+        if cross(offset, r).abs() > f32::EPSILON {
+            return false;
+        }
+        let length = r.length_squared();
+        if length < f32::EPSILON {
+            return (a1 - b1).length_squared() < f32::EPSILON;
+        }
+        let t0 = offset.dot(r) / length;
+        let t1 = t0 + s.dot(r) / length;
+        return t0.min(t1) <= 1.0 && t0.max(t1) >= 0.0;
+    }
+
+    let t = cross(offset, s) / denominator;
+    let u = cross(offset, r) / denominator;
+    (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+}
+
+/// Where two segments cross, if they do at a single point.
+fn crossing(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> Option<Vec2> {
+    let r = a2 - a1;
+    let s = b2 - b1;
+    let denominator = cross(r, s);
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+    let offset = b1 - a1;
+    let t = cross(offset, s) / denominator;
+    let u = cross(offset, r) / denominator;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then_some(a1 + r * t)
+}
+
+fn edges(polygon: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/// Whether a segment touches a polygon: crosses an edge or lies inside it.
+/// This is what catches fast bullets that would skip clean over a small
+/// asteroid between two ticks.
+pub fn segment_hits_polygon(start: Vec2, end: Vec2, polygon: &[Vec2]) -> bool {
+    point_in_polygon(start, polygon)
+        || edges(polygon).any(|(a, b)| segments_intersect(start, end, a, b))
+}
+
+/// Where two overlapping polygons touch and which way they push: the middle
+/// of the outermost pair of edge crossings, and the unit normal across the
+/// line between them, pointing from `a` towards `b`. None if the edges
+/// don't cross at least twice, e.g. when one sits wholly inside the other.
+pub fn contact(a: &[Vec2], b: &[Vec2]) -> Option<(Vec2, Vec2)> {
+    let crossings: Vec<Vec2> = edges(a)
+        .flat_map(|(a1, a2)| edges(b).filter_map(move |(b1, b2)| crossing(a1, a2, b1, b2)))
+        .collect();
+    let (first, last) = crossings.iter()
+        .flat_map(|p| crossings.iter().map(move |q| (*p, *q)))
+        .max_by(|(p1, q1), (p2, q2)| (*p1 - *q1).length_squared().total_cmp(&(*p2 - *q2).length_squared()))?;
+    let normal = (last - first).perp().try_normalize()?;
+
+    let centre = |polygon: &[Vec2]| polygon.iter().copied().sum::<Vec2>() / polygon.len() as f32;
+    let towards_b = centre(b) - centre(a);
+    let normal = if normal.dot(towards_b) < 0.0 { -normal } else { normal };
+    Some(((first + last) / 2.0, normal))
+}
+
+/// Whether no two edges of the polygon cross, apart from neighbours
+/// sharing their corner.
+pub fn is_simple(polygon: &[Vec2]) -> bool {
+    let count = polygon.len();
+    let edges: Vec<(Vec2, Vec2)> = edges(polygon).collect();
+    (0..count).all(|i| {
+        (i + 2..count)
+            .filter(|&j| (j + 1) % count != i)
+            .all(|j| !segments_intersect(edges[i].0, edges[i].1, edges[j].0, edges[j].1))
+    })
+}
+
+/// Whether two polygons overlap: some edges cross, or one sits wholly
+/// inside the other.
+pub fn polygons_overlap(a: &[Vec2], b: &[Vec2]) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    edges(a).any(|(a1, a2)| edges(b).any(|(b1, b2)| segments_intersect(a1, a2, b1, b2)))
+        || point_in_polygon(a[0], b)
+        || point_in_polygon(b[0], a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(centre: Vec2, half: f32) -> Vec<Vec2> {
+        transform(
+            &[Vec2::new(-half, -half), Vec2::new(half, -half), Vec2::new(half, half), Vec2::new(-half, half)],
+            centre,
+            0.0,
+        )
+    }
+
+    #[test]
+    fn points_inside_a_concave_outline() {
+        // A "C" opening to the right
+        let c = [
+            Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.0), Vec2::new(3.0, 1.0), Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0), Vec2::new(3.0, 2.0), Vec2::new(3.0, 3.0), Vec2::new(0.0, 3.0),
+        ];
+        assert!(point_in_polygon(Vec2::new(0.5, 1.5), &c));
+        assert!(!point_in_polygon(Vec2::new(2.0, 1.5), &c));
+    }
+
+    #[test]
+    fn wraps_to_the_nearest_image() {
+        let delta = wrapped_delta(Vec2::new(790.0, 10.0), Vec2::new(10.0, 590.0), 800.0, 600.0);
+        assert!((delta - Vec2::new(20.0, -20.0)).length() < 1e-3);
+        assert_eq!(wrapped(500.0, 0.0), 500.0);
+    }
+
+    #[test]
+    fn segments_cross_touch_or_miss() {
+        let (a, b) = (Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+        assert!(segments_intersect(a, b, Vec2::new(0.0, 2.0), Vec2::new(2.0, 0.0)));
+        assert!(segments_intersect(a, b, Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0)));
+        assert!(!segments_intersect(a, b, Vec2::new(1.0, 0.0), Vec2::new(3.0, 2.0)));
+    }
+
+    #[test]
+    fn fast_bullets_cant_tunnel() {
+        let target = square(Vec2::ZERO, 5.0);
+        assert!(segment_hits_polygon(Vec2::new(-50.0, 0.0), Vec2::new(50.0, 0.0), &target));
+        assert!(!segment_hits_polygon(Vec2::new(-50.0, 20.0), Vec2::new(50.0, 20.0), &target));
+    }
+
+    #[test]
+    fn outlines_overlap_by_edges_or_containment() {
+        let a = square(Vec2::ZERO, 5.0);
+        assert!(polygons_overlap(&a, &square(Vec2::new(8.0, 0.0), 5.0)));
+        assert!(polygons_overlap(&a, &square(Vec2::ZERO, 1.0)));
+        assert!(!polygons_overlap(&a, &square(Vec2::new(20.0, 0.0), 5.0)));
+    }
+
+    #[test]
+    fn contact_between_overlapping_outlines() {
+        // A tall one poking into the right side: its edge crosses a's twice
+        let a = square(Vec2::ZERO, 5.0);
+        let tall = [Vec2::new(3.0, -20.0), Vec2::new(13.0, -20.0), Vec2::new(13.0, 20.0), Vec2::new(3.0, 20.0)];
+        let (point, normal) = contact(&a, &tall).unwrap();
+        assert!((point - Vec2::new(3.0, 0.0)).length() < 1e-3);
+        assert!((normal - Vec2::X).length() < 1e-3);
+    }
+
+    #[test]
+    fn spots_self_crossing_outlines() {
+        assert!(is_simple(&square(Vec2::ZERO, 1.0)));
+        let bow_tie = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)];
+        assert!(!is_simple(&bow_tie));
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ * Einstellungen (Settings): a plain text file, one "name value" per line,
+ * '#' starts a comment. A missing file or setting means the default, which
+ * is always the classic arcade behaviour.
+ *
+ *   # asteroids config
+ *   asteroid_collisions on
+ *   large_fragments 3
+ *   large_fragment_speed 1.2
+ *   volume 0.5
+ *
+ * Everything in here that changes the simulation (the `rules`) is written
+ * into replays as well, so a session plays back the same whatever the
+ * config says now.
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::asteroid::{AsteroidSize, SplitRule};
+use crate::ship::{FireRule, TurnRule};
+
+const HEADER: &str = "# asteroids config";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub asteroid_collisions: bool, // asteroids bounce off each other instead of passing through
+    pub large_split: SplitRule,    // what a large asteroid breaks into
+    pub medium_split: SplitRule,
+    pub turn: TurnRule,            // how the ship turns while a rotate key is held
+    pub fire: FireRule,            // how fast the ship can shoot
+    pub extra_life_every: i32,     // points per bonus life; 0 for none
+    pub max_lives: i32,            // bonus lives stop here
+    pub sound: bool,  // off mutes everything
+    pub volume: f32,  // 0 to 1
+    pub gamepad_deadzone: f32, // how far the stick must go before the ship turns, 0 to 1
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let classic = SplitRule { fragments: 2, speed: 1.5 };
+        Config {
+            asteroid_collisions: false,
+            large_split: classic,
+            medium_split: classic,
+            turn: TurnRule { speed: 4.0, acceleration: 0.0 },
+            fire: FireRule { cooldown: 0.1, max_bullets: 4, auto: false },
+            extra_life_every: 10_000,
+            max_lives: 10,
+            sound: true,
+            volume: 0.8,
+            gamepad_deadzone: 0.3,
+        }
+    }
+}
+
+impl Config {
+    /// No file yet just means the defaults.
+    pub fn load(path: &Path) -> io::Result<Config> {
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    pub fn parse(text: &str) -> io::Result<Config> {
+        let mut config = Config::default();
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let bad = || io::Error::new(io::ErrorKind::InvalidData, format!("bad config line: {}", line));
+            let (name, value) = line.split_once(char::is_whitespace).ok_or_else(bad)?;
+            config.set(name, value.trim()).ok_or_else(bad)?;
+        }
+        Ok(config)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (name, value) in self.settings() {
+            text.push_str(&format!("{} {}\n", name, value));
+        }
+        text
+    }
+
+    /// Every setting by name, with its value as it would be written out.
+    pub fn settings(&self) -> Vec<(&'static str, String)> {
+        let mut settings = self.rules();
+        settings.push(("sound", on_off(self.sound)));
+        settings.push(("volume", self.volume.to_string()));
+        settings.push(("gamepad_deadzone", self.gamepad_deadzone.to_string()));
+        settings
+    }
+
+    /// Just the settings that change how the game plays out.
+    pub fn rules(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("asteroid_collisions", on_off(self.asteroid_collisions)),
+            ("large_fragments", self.large_split.fragments.to_string()),
+            ("large_fragment_speed", self.large_split.speed.to_string()),
+            ("medium_fragments", self.medium_split.fragments.to_string()),
+            ("medium_fragment_speed", self.medium_split.speed.to_string()),
+            ("turn_speed", self.turn.speed.to_string()),
+            ("turn_acceleration", self.turn.acceleration.to_string()),
+            ("fire_cooldown", self.fire.cooldown.to_string()),
+            ("max_bullets", self.fire.max_bullets.to_string()),
+            ("auto_fire", on_off(self.fire.auto)),
+            ("extra_life_every", self.extra_life_every.to_string()),
+            ("max_lives", self.max_lives.to_string()),
+        ]
+    }
+
+    /// Change one setting by name; None for an unknown name or a value that
+    /// doesn't fit it.
+    pub fn set(&mut self, name: &str, value: &str) -> Option<()> {
+        match name {
+            "asteroid_collisions" => self.asteroid_collisions = parse_on_off(value)?,
+            "large_fragments" => self.large_split.fragments = parse_fragments(value)?,
+            "large_fragment_speed" => self.large_split.speed = parse_non_negative(value)?,
+            "medium_fragments" => self.medium_split.fragments = parse_fragments(value)?,
+            "medium_fragment_speed" => self.medium_split.speed = parse_non_negative(value)?,
+            "turn_speed" => self.turn.speed = parse_non_negative(value)?,
+            "turn_acceleration" => self.turn.acceleration = parse_non_negative(value)?,
+            "fire_cooldown" => self.fire.cooldown = parse_non_negative(value)?,
+            "max_bullets" => self.fire.max_bullets = value.parse().ok().filter(|count| *count >= 1)?,
+            "auto_fire" => self.fire.auto = parse_on_off(value)?,
+            "extra_life_every" => self.extra_life_every = value.parse().ok().filter(|points| *points >= 0)?,
+            "max_lives" => self.max_lives = value.parse().ok().filter(|lives| *lives >= 1)?,
+            "sound" => self.sound = parse_on_off(value)?,
+            "volume" => self.volume = parse_non_negative(value).filter(|volume| *volume <= 1.0)?,
+            "gamepad_deadzone" => self.gamepad_deadzone = parse_non_negative(value).filter(|zone| *zone < 1.0)?,
+            _ => return None,
+        }
+        Some(())
+    }
+
+    /// How asteroids of `size` break up; small ones just shatter.
+    pub fn split_rule(&self, size: AsteroidSize) -> Option<SplitRule> {
+        match size {
+            AsteroidSize::Large => Some(self.large_split),
+            AsteroidSize::Medium => Some(self.medium_split),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+fn on_off(value: bool) -> String {
+    if value { "on" } else { "off" }.to_string()
+}
+
+/// Up to eight; zero makes the size class shatter like a small asteroid.
+fn parse_fragments(value: &str) -> Option<u32> {
+    value.parse().ok().filter(|count| *count <= 8)
+}
+
+fn parse_non_negative(value: &str) -> Option<f32> {
+    value.parse().ok().filter(|number: &f32| number.is_finite() && *number >= 0.0)
+}
+
+fn parse_on_off(value: &str) -> Option<bool> {
+    match value {
+        "on" | "true" | "yes" => Some(true),
+        "off" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_settings_and_comments() {
+        let config = Config::parse("# asteroids config\nasteroid_collisions on # bounce\nlarge_fragments 3\n\nvolume 0.5\n").unwrap();
+        assert!(config.asteroid_collisions);
+        assert_eq!(config.large_split.fragments, 3);
+        assert_eq!(config.volume, 0.5);
+        assert_eq!(config.medium_split, Config::default().medium_split);
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut config = Config::default();
+        config.set("asteroid_collisions", "yes").unwrap();
+        config.set("medium_fragment_speed", "0.75").unwrap();
+        config.set("turn_acceleration", "12").unwrap();
+        config.set("auto_fire", "on").unwrap();
+        config.set("sound", "off").unwrap();
+        assert_eq!(Config::parse(&config.to_text()).unwrap(), config);
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_bad_values() {
+        for line in ["warp_speed 9", "large_fragments 9", "volume 2", "max_lives 0", "asteroid_collisions maybe", "sound"] {
+            let err = Config::parse(line).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", line);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// Spielsteuerung (Gamepad): buttons onto the same actions as the keyboard,
+// and the left stick for turning by as much as it's pushed.
+
+use ggez::event::Button;
+
+use crate::input::Action;
+
+/// The stick's turn is rounded to this many steps each way, so a stick
+/// that never quite sits still doesn't fill the replay with tiny changes.
+const TURN_STEPS: f32 = 20.0;
+
+/// Triggers thrust, the face buttons fire and jump, Start pauses.
+pub fn action_for_button(button: Button) -> Option<Action> {
+    match button {
+        Button::RightTrigger2 | Button::RightTrigger | Button::DPadUp => Some(Action::Thrust),
+        Button::South | Button::West => Some(Action::Fire),
+        Button::East | Button::North => Some(Action::Hyperspace),
+        Button::DPadLeft => Some(Action::RotateLeft),
+        Button::DPadRight => Some(Action::RotateRight),
+        Button::Start => Some(Action::Pause),
+        _ => None,
+    }
+}
+
+/// The stick turns the ship as far as it is pushed: not at all inside the
+/// deadzone, at full speed against the rim, evenly in between.
+#[derive(Default)]
+pub struct Stick {
+    turn: f32,
+}
+
+impl Stick {
+    /// The turn for the stick at `x`, from -1 (left) to 1 (right), or None
+    /// if that's no change from the last one.
+    pub fn deflect(&mut self, x: f32, deadzone: f32) -> Option<f32> {
+        let beyond = ((x.abs() - deadzone) / (1.0 - deadzone)).clamp(0.0, 1.0);
+        let amount = (beyond * TURN_STEPS).round() / TURN_STEPS;
+        let turn = if x < 0.0 && amount > 0.0 { -amount } else { amount };
+        if turn == self.turn {
+            return None;
+        }
+        self.turn = turn;
+        Some(turn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_inside_the_deadzone() {
+        let mut stick = Stick::default();
+        assert_eq!(stick.deflect(0.2, 0.3), None);
+        assert_eq!(stick.deflect(-0.3, 0.3), None);
+    }
+
+    #[test]
+    fn turns_as_far_as_its_pushed() {
+        let mut stick = Stick::default();
+        assert_eq!(stick.deflect(0.65, 0.3), Some(0.5));
+        assert_eq!(stick.deflect(0.66, 0.3), None);
+        assert_eq!(stick.deflect(-1.0, 0.3), Some(-1.0));
+        assert_eq!(stick.deflect(0.0, 0.3), Some(0.0));
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * Die Bestenliste (The high-score table): top ten scores with arcade-style
+ * three-letter initials, stored as plain text, one "AXL 12345" per line.
+ *
+ * "The Answer to the Great Question... Of Life, the Universe and
+ * Everything... Is... Forty-two."
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const MAX_ENTRIES: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: i32,
+}
+
+#[derive(Default)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>, // best first
+}
+
+impl HighScores {
+    /// A missing or unreadable file just means nobody has played yet.
+    pub fn load(path: &Path) -> HighScores {
+        match fs::read_to_string(path) {
+            Ok(text) => HighScores::parse(&text),
+            Err(_) => HighScores::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    pub fn parse(text: &str) -> HighScores {
+        let mut entries: Vec<HighScore> = text
+            .lines()
+            .filter_map(|line| {
+                let (initials, score) = line.trim().split_once(' ')?;
+                Some(HighScore {
+                    initials: initials.to_string(),
+                    score: score.trim().parse().ok()?,
+                })
+            })
+            .collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        entries.truncate(MAX_ENTRIES);
+        HighScores { entries }
+    }
+
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{} {}\n", entry.initials, entry.score))
+            .collect()
+    }
+
+    /// Whether `score` would make it onto the table.
+    pub fn qualifies(&self, score: i32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_ENTRIES
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Insert below any equal scores (first come, first served) and return
+    /// the 0-based rank.
+    pub fn insert(&mut self, initials: &str, score: i32) -> usize {
+        let rank = self.entries.iter().take_while(|entry| entry.score >= score).count();
+        self.entries.insert(rank, HighScore {
+            initials: initials.to_string(),
+            score,
+        });
+        self.entries.truncate(MAX_ENTRIES);
+        rank
+    }
+}
+
+/// Three letters, picked one at a time by cycling through A-Z.
+pub struct InitialsEntry {
+    pub letters: [u8; 3],
+    pub cursor: usize,
+}
+
+impl InitialsEntry {
+    pub fn new() -> InitialsEntry {
+        InitialsEntry {
+            letters: [b'A'; 3],
+            cursor: 0,
+        }
+    }
+
+    /// Step the current letter forwards or backwards, wrapping Z -> A.
+    pub fn cycle(&mut self, delta: i32) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = b'A' + ((*letter - b'A') as i32 + delta).rem_euclid(26) as u8;
+    }
+
+    /// Type a letter straight in and move on.
+    pub fn set(&mut self, letter: char) -> bool {
+        if !letter.is_ascii_alphabetic() {
+            return false;
+        }
+        self.letters[self.cursor] = letter.to_ascii_uppercase() as u8;
+        self.confirm()
+    }
+
+    /// Lock in the current letter; true once all three are done.
+    pub fn confirm(&mut self) -> bool {
+        self.cursor += 1;
+        self.cursor >= self.letters.len()
+    }
+
+    pub fn back(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn initials(&self) -> String {
+        self.letters.iter().map(|&letter| letter as char).collect()
+    }
+}
+
+impl Default for InitialsEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_best_first_and_skips_junk() {
+        let scores = HighScores::parse("AAA 100\nnonsense\nBBB 300\nCCC lots\nDDD 200\n");
+        let table: Vec<(&str, i32)> = scores.entries.iter().map(|e| (e.initials.as_str(), e.score)).collect();
+        assert_eq!(table, vec![("BBB", 300), ("DDD", 200), ("AAA", 100)]);
+    }
+
+    #[test]
+    fn keeps_only_the_top_ten() {
+        let text: String = (1..=15).map(|score| format!("AAA {}\n", score)).collect();
+        let scores = HighScores::parse(&text);
+        assert_eq!(scores.entries.len(), MAX_ENTRIES);
+        assert_eq!(scores.entries.last().unwrap().score, 6);
+        assert!(!scores.qualifies(6));
+        assert!(scores.qualifies(7));
+    }
+
+    #[test]
+    fn ties_go_below_and_round_trip() {
+        let mut scores = HighScores::parse("AAA 100\n");
+        assert_eq!(scores.insert("BBB", 100), 1);
+        assert_eq!(HighScores::parse(&scores.to_text()).entries, scores.entries);
+    }
+}
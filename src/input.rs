@@ -0,0 +1,143 @@
+// ἔλεγχος
+// Was der Spieler tun kann (What the player can do), independent of which
+// key or device asked for it.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    RotateLeft,
+    RotateRight,
+    Thrust,
+    Fire,
+    Hyperspace,
+    Pause, // for the shell around the game; the world never sees it
+    Restart,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    Pressed(Action),
+    Released(Action),
+    /// How far a stick turns the ship, from -1 (full left) to 1 (full
+    /// right), until the next one. Adds to the rotate keys.
+    Turn(f32),
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Thrust,
+        Action::Fire,
+        Action::Hyperspace,
+        Action::Pause,
+        Action::Restart,
+    ];
+
+    /// Stable name used in replay and bindings files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::RotateLeft => "RotateLeft",
+            Action::RotateRight => "RotateRight",
+            Action::Thrust => "Thrust",
+            Action::Fire => "Fire",
+            Action::Hyperspace => "Hyperspace",
+            Action::Pause => "Pause",
+            Action::Restart => "Restart",
+        }
+    }
+
+    /// For menus.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::RotateLeft => "ROTATE LEFT",
+            Action::RotateRight => "ROTATE RIGHT",
+            Action::Thrust => "THRUST",
+            Action::Fire => "FIRE",
+            Action::Hyperspace => "HYPERSPACE",
+            Action::Pause => "PAUSE",
+            Action::Restart => "PLAY AGAIN",
+        }
+    }
+
+    /// Whether the action goes to the `World` (and into replays).
+    pub fn for_world(&self) -> bool {
+        *self != Action::Pause
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// What is holding each action down right now, by whatever `S` tells keys
+/// and buttons apart. With two keys on one action, letting go of one of
+/// them mustn't let go of the action.
+pub struct Held<S> {
+    sources: Vec<(Action, S)>,
+}
+
+impl<S> Default for Held<S> {
+    fn default() -> Self {
+        Held { sources: Vec::new() }
+    }
+}
+
+impl<S: PartialEq> Held<S> {
+    pub fn press(&mut self, action: Action, source: S) {
+        if !self.sources.iter().any(|(_, held)| *held == source) {
+            self.sources.push((action, source));
+        }
+    }
+
+    /// The action `source` was holding down, if nothing else still is.
+    pub fn release(&mut self, source: &S) -> Option<Action> {
+        let at = self.sources.iter().position(|(_, held)| held == source)?;
+        let (action, _) = self.sources.swap_remove(at);
+        (!self.is_held(action)).then_some(action)
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.sources.iter().any(|(held, _)| *held == action)
+    }
+
+    /// Let go of every source that `which` picks out, e.g. all the buttons
+    /// of a pad that went away. Returns the actions nothing holds any more.
+    pub fn release_where(&mut self, which: impl Fn(&S) -> bool) -> Vec<Action> {
+        let mut released: Vec<Action> = Vec::new();
+        for (action, _) in self.sources.iter().filter(|(_, source)| which(source)) {
+            if !released.contains(action) {
+                released.push(*action);
+            }
+        }
+        self.sources.retain(|(_, source)| !which(source));
+        released.retain(|action| !self.is_held(*action));
+        released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_once_the_last_source_lets_go() {
+        let mut held = Held::default();
+        held.press(Action::RotateLeft, 'a');
+        held.press(Action::RotateLeft, '<');
+        assert_eq!(held.release(&'a'), None);
+        assert!(held.is_held(Action::RotateLeft));
+        assert_eq!(held.release(&'<'), Some(Action::RotateLeft));
+        assert_eq!(held.release(&'<'), None);
+    }
+
+    #[test]
+    fn letting_go_of_a_device() {
+        let mut held = Held::default();
+        held.press(Action::Thrust, 1);
+        held.press(Action::Thrust, 12);
+        held.press(Action::Fire, 13);
+        held.press(Action::Fire, 14);
+        assert_eq!(held.release_where(|source| *source > 10), vec![Action::Fire]);
+        assert!(held.is_held(Action::Thrust));
+    }
+}
@@ -0,0 +1,38 @@
+/*
+ * Asteroids simulation core.
+ *
+ * Everything that moves lives in here and advances with an explicit `dt`
+ * and arena size, so the game runs without a window - for tests, bots and
+ * benchmarks on machines without a GPU. `main.rs` is only the ggez shell
+ * around it.
+ *
+ * "Don't Panic!"
+ */
+
+pub mod asteroid;
+pub mod audio;
+pub mod bindings;
+pub mod bullet;
+pub mod collision;
+pub mod config;
+pub mod gamepad;
+pub mod highscores;
+pub mod input;
+pub mod particle;
+pub mod render;
+pub mod replay;
+pub mod saucer;
+pub mod shape;
+pub mod ship;
+pub mod spatial;
+pub mod state;
+pub mod world;
+
+pub use asteroid::{Asteroid, AsteroidSize};
+pub use bullet::Bullet;
+pub use config::Config;
+pub use input::{Action, InputEvent};
+pub use particle::Particle;
+pub use saucer::{Saucer, SaucerSize};
+pub use ship::Ship;
+pub use world::World;
@@ -13,673 +13,681 @@
  * "In the beginning the Universe was created. This has made a lot of people 
  * very angry and been widely regarded as a bad move." - Douglas Adams
  * 
- * Controls:
- * ← → to rotate
- * ↑ to thrust
+ * Controls (change them in the options, or in bindings.txt):
+ * ← → or A D to rotate
+ * ↑ or W to thrust
  * SPACE to shoot
- * R to restart when game over
+ * ↓ or S for hyperspace (risky)
+ * P or ESC to pause
+ * M to mute
+ * or a gamepad: stick or d-pad to rotate, right trigger to thrust, A to
+ *   shoot, B for hyperspace, Start to pause
+ * SPACE or R to play again when game over
+ * ← → (or type) and ENTER to put your initials on the high-score table
+ *
+ * Every session is recorded to asteroids_replay.txt; play one back with
+ * --replay <file>. Settings live in config.txt in the user data directory,
+ * or wherever --config <file> points.
  * 
  * "Time is an illusion. Gameplay doubly so." - Douglas Adams
  */
 
+use asteroids::audio::Audio;
+use asteroids::bindings::{self, Bindings};
+use asteroids::config::Config;
+use asteroids::gamepad::{self, Stick};
+use asteroids::highscores::{HighScores, InitialsEntry};
+use asteroids::input::{Action, Held, InputEvent};
+use asteroids::render::Batch;
+use asteroids::replay::{Replay, ReplayEvent, ReplayPlayer};
+use asteroids::ship::ShipMeshes;
+use asteroids::{Bullet, Particle};
+use asteroids::state::{GameState, Menu, OptionItem, PauseItem};
+use asteroids::world::{playable_size, World, TICK_DT};
 use ggez::{
-    event,
-    graphics::{Canvas, Color, DrawMode, DrawParam, Drawable, Mesh, Text, TextFragment},
+    event::{self, Axis, Button},
+    graphics::{Canvas, Color, DrawParam, Drawable, Text, TextFragment},
+    input::gamepad::GamepadId,
     input::keyboard::{KeyCode, KeyInput},
     Context, GameResult,
 };
 use glam::Vec2;
-use rand::Rng;
-use std::f32::consts::PI;
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::env;
+use std::path::PathBuf;
 use std::time::SystemTime;
 use std::panic;
 
-// ἀρχὴ ἥμισυ παντός
-struct Particle {
-    position: Vec2,
-    velocity: Vec2,
-    lifetime: f32,
-    size: f32,
+/// Seconds the game-over screens ignore keys for, so fire pressed just as
+/// the last ship went doesn't skip them.
+const ARM_DELAY: f32 = 0.5;
+
+/// A key or a pad button: whatever can hold an action down.
+#[derive(Clone, Copy, PartialEq)]
+enum Control {
+    Key(KeyCode),
+    Button(Button),
 }
 
-impl Particle {
-    fn update(&mut self, dt: f32) {
-        self.position += self.velocity * dt;
-        self.lifetime -= dt;
-        self.size *= 0.95;
-    }
-
-    fn draw(&self, canvas: &mut Canvas, ctx: &Context) -> GameResult {
-        let mesh = Mesh::new_circle(
-            ctx,
-            DrawMode::fill(),
-            self.position,
-            self.size,
-            0.1,
-            Color::new(1.0, 1.0, 1.0, self.lifetime),
-        )?;
-        canvas.draw(&mesh, DrawParam::default());
-        Ok(())
+// Kommandozeile (Command line)
+struct Options {
+    seed: u64,
+    replay: Option<PathBuf>,
+    record: PathBuf,
+    config: Option<PathBuf>, // instead of the one in the user data directory
+}
+
+impl Options {
+    fn from_args() -> GameResult<Options> {
+        let mut seed = None;
+        let mut replay = None;
+        let mut record = PathBuf::from("asteroids_replay.txt");
+        let mut config = None;
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().unwrap_or_default();
+                    seed = Some(value.parse().map_err(|_| {
+                        ggez::GameError::CustomError(format!("Bad --seed value {:?}: expected a whole number", value))
+                    })?);
+                }
+                "--replay" => replay = args.next().map(PathBuf::from),
+                "--config" => config = args.next().map(PathBuf::from),
+                "--record" => {
+                    if let Some(path) = args.next() {
+                        record = PathBuf::from(path);
+                    }
+                }
+                other => eprintln!("Ignoring unknown argument: {}", other),
+            }
+        }
+
+        Ok(Options {
+            // No seed given: take one from the clock, it's logged anyway
+            seed: seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos() as u64
+            }),
+            replay,
+            record,
+            config,
+        })
     }
 }
 
-// τὰ πάντα ῥεῖ
+// Thin ggez shell around the simulation in `World`
 struct MainState {
-    asteroids: Vec<Asteroid>,
-    ship: Ship,
-    bullets: Vec<Bullet>,
-    game_over: bool,
-    game_over_timer: f32,
-    lives: i32,
-    score: i32,
+    state: GameState,
+    world: World,
+    config: Config,
+    config_path: PathBuf, // where sound settings changed in the options go
+    seed: u64, // for the next session started from the title screen
     displayed_score: i32,
-    respawn_timer: f32,
-    particles: Vec<Particle>,
-    score_popup: Option<(String, Vec2, f32)>, // text, position, lifetime
-    debug_info: String,
+    accumulator: f32, // unsimulated time carried over to the next frame
+    recording: Option<Replay>,      // live sessions: inputs so far
+    record_path: PathBuf,
+    playback: Option<ReplayPlayer>, // --replay: inputs come from the file instead
+    high_scores: HighScores,
+    high_scores_path: PathBuf,
+    initials: Option<InitialsEntry>, // set while a new high score is being signed
+    arming: f32,                     // seconds until the game-over screens take keys
+    key_repeating: bool,             // the last key down was the OS repeating a held key
+    pause_menu: Menu<PauseItem>,
+    options_menu: Option<Menu<OptionItem>>, // the options page is open
+    controls_menu: Option<Menu<Action>>,    // the controls page is open
+    rebinding: Option<Action>,              // waiting for a key to add to this action
+    bindings: Bindings,
+    bindings_path: PathBuf,
+    held: Held<Control>, // what's holding the ship's controls down
+    stick: Stick,
+    controller: Option<String>, // name of the gamepad in use, if one is plugged in
+    show_debug: bool,
+    audio: Audio,
+    ship_meshes: ShipMeshes,
+    bullet_batch: Batch,
+    dot_batch: Batch,  // particles
+    line_batch: Batch, // debris
 }
 
-struct Asteroid {
-    position: Vec2,
-    velocity: Vec2,
-    points: Vec<Vec2>,
-    rotation: f32,
-    rotation_speed: f32,
-    size: AsteroidSize,
-}
+impl MainState {
+    fn new(ctx: &mut Context, options: &Options) -> GameResult<MainState> {
+        // Set up panic handler for crash reporting
+        panic::set_hook(Box::new(|panic_info| {
+            let timestamp = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            
+            let crash_msg = format!(
+                "\n[Crash Report {}]\nPanic occurred: {}\n",
+                timestamp,
+                panic_info
+            );
 
-// μέτρον ἄριστον
-#[derive(Clone, Copy)]
-enum AsteroidSize {
-    Large,   // Großer Asteroid
-    Medium,  // Mittlerer Asteroid
-    Small,   // Kleiner Asteroid
-}
+            if let Ok(mut file) = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open("asteroids_crash.log")
+            {
+                let _ = writeln!(file, "{}", crash_msg);
+            }
+        }));
 
-impl AsteroidSize {
-    fn radius(&self) -> f32 {
-        match self {
-            AsteroidSize::Large => 80.0,
-            AsteroidSize::Medium => 40.0,
-            AsteroidSize::Small => 20.0,
-        }
-    }
+        let high_scores_path = ctx.fs.user_data_dir().join("highscores.txt");
+        let high_scores = HighScores::load(&high_scores_path);
 
-    fn points(&self) -> i32 {
-        match self {
-            AsteroidSize::Large => 20,
-            AsteroidSize::Medium => 50,
-            AsteroidSize::Small => 100,
-        }
+        let config_path = options.config.clone()
+            .unwrap_or_else(|| ctx.fs.user_data_dir().join("config.txt"));
+        let config = Config::load(&config_path).map_err(|e| {
+            ggez::GameError::CustomError(format!("Could not load config {}: {}", config_path.display(), e))
+        })?;
+
+        let bindings_path = ctx.fs.user_data_dir().join("bindings.txt");
+        let bindings = Bindings::load(&bindings_path).map_err(|e| {
+            ggez::GameError::CustomError(format!("Could not load bindings {}: {}", bindings_path.display(), e))
+        })?;
+
+        let audio = Audio::new(ctx, config.volume, !config.sound)?;
+
+        let (width, height) = ctx.gfx.drawable_size();
+        let mut state = MainState {
+            state: GameState::Title,
+            world: World::attract(width, height, options.seed, config.clone()),
+            config,
+            config_path,
+            seed: options.seed,
+            displayed_score: 0,
+            accumulator: 0.0,
+            recording: None,
+            record_path: options.record.clone(),
+            playback: None,
+            high_scores,
+            high_scores_path,
+            initials: None,
+            arming: 0.0,
+            key_repeating: false,
+            pause_menu: Menu::pause(),
+            options_menu: None,
+            controls_menu: None,
+            rebinding: None,
+            bindings,
+            bindings_path,
+            held: Held::default(),
+            stick: Stick::default(),
+            controller: None,
+            show_debug: true,
+            audio,
+            ship_meshes: ShipMeshes::new(ctx)?,
+            bullet_batch: Batch::new(ctx, Bullet::mesh(ctx)?),
+            dot_batch: Batch::new(ctx, Particle::dot_mesh(ctx)?),
+            line_batch: Batch::new(ctx, Particle::line_mesh(ctx)?),
+        };
+
+        // A replay skips the title screen and goes straight into the game
+        if let Some(path) = &options.replay {
+            let replay = Replay::load(path).map_err(|e| {
+                ggez::GameError::CustomError(format!("Could not load replay {}: {}", path.display(), e))
+            })?;
+            state.world = World::new(replay.width, replay.height, replay.seed, replay.config.clone());
+            state.world.log_debug(&format!("Playing back {} (seed {})", path.display(), replay.seed));
+            state.playback = Some(ReplayPlayer::new(replay));
+            state.state = GameState::Playing;
+        }
+        Ok(state)
     }
 
-    fn next_size(&self) -> Option<AsteroidSize> {
-        match self {
-            AsteroidSize::Large => Some(AsteroidSize::Medium),
-            AsteroidSize::Medium => Some(AsteroidSize::Small),
-            AsteroidSize::Small => None,
-        }
+    // Zustandswechsel (State changes)
+    fn start_session(&mut self, ctx: &Context) {
+        let (width, height) = ctx.gfx.drawable_size();
+        self.write_log();
+        self.held = Held::default();
+        self.world = World::new(width, height, self.seed, self.config.clone());
+        self.world.log_debug(&format!("New session. Seed: {} (replay with --seed {})", self.seed, self.seed));
+        self.recording = Some(Replay::new(self.seed, width, height, self.config.clone()));
+        self.seed = self.seed.wrapping_add(1);
+        self.displayed_score = 0;
+        self.accumulator = 0.0;
+        self.state = GameState::Playing;
     }
-}
 
-// κίνησις πάντων
-struct Ship {
-    position: Vec2,
-    velocity: Vec2,
-    rotation: f32,
-    thrust: bool,
-    invulnerable: bool,
-    invulnerable_timer: f32,
-}
+    fn back_to_title(&mut self, ctx: &Context) {
+        self.save_recording();
+        self.recording = None;
+        self.playback = None;
+        let (width, height) = ctx.gfx.drawable_size();
+        self.write_log();
+        self.held = Held::default();
+        self.world = World::attract(width, height, self.seed, self.config.clone());
+        self.state = GameState::Title;
+    }
 
-struct Bullet {
-    position: Vec2,
-    velocity: Vec2,
-    lifetime: f32,
-}
+    fn pause(&mut self) {
+        self.pause_menu = Menu::pause();
+        self.options_menu = None;
+        self.controls_menu = None;
+        self.rebinding = None;
+        self.state = GameState::Paused;
+    }
 
-impl Asteroid {
-    fn new_with_size(ctx: &Context, size: AsteroidSize) -> Self {
-        let mut rng = rand::thread_rng();
-        
-        let (width, height) = ctx.gfx.drawable_size();
-        let position = if rng.gen_bool(0.5) {
-            Vec2::new(
-                if rng.gen_bool(0.5) { 0.0 } else { width },
-                rng.gen_range(0.0..height),
-            )
+    fn resume(&mut self) {
+        self.state = if self.world.respawn_timer > 0.0 {
+            GameState::Respawning
         } else {
-            Vec2::new(
-                rng.gen_range(0.0..width),
-                if rng.gen_bool(0.5) { 0.0 } else { height },
-            )
+            GameState::Playing
         };
+    }
 
-        let speed = rng.gen_range(50.0..150.0);
-        let angle = rng.gen_range(0.0..2.0 * PI);
-        let velocity = Vec2::new(angle.cos() * speed, angle.sin() * speed);
+    fn play_again(&mut self) {
+        self.apply_input(InputEvent::Pressed(Action::Restart));
+        self.state = GameState::Playing;
+    }
 
-        let num_points = rng.gen_range(6..12);
-        let mut points = Vec::new();
-        let base_radius = size.radius();
-        
-        for i in 0..num_points {
-            let angle = (i as f32 / num_points as f32) * 2.0 * PI;
-            let radius = base_radius * rng.gen_range(0.5..1.5);
-            points.push(Vec2::new(angle.cos() * radius, angle.sin() * radius));
+    /// Move between Playing, Respawning and GameOver as the world dictates.
+    fn follow_world(&mut self) {
+        let previous = self.state;
+        self.state = match self.state {
+            GameState::Playing | GameState::Respawning if self.world.game_over => {
+                if self.playback.is_none() && self.high_scores.qualifies(self.world.score) {
+                    self.initials = Some(InitialsEntry::new());
+                    GameState::HighScoreEntry
+                } else {
+                    GameState::GameOver
+                }
+            }
+            GameState::Playing | GameState::Respawning => {
+                if self.world.respawn_timer > 0.0 {
+                    GameState::Respawning
+                } else {
+                    GameState::Playing
+                }
+            }
+            // A replay restarted the game
+            GameState::GameOver if !self.world.game_over => GameState::Playing,
+            other => other,
+        };
+        if self.state.after_game() && !previous.after_game() {
+            self.arming = ARM_DELAY;
         }
+    }
 
-        Asteroid {
-            position,
-            velocity,
-            points,
-            rotation: 0.0,
-            rotation_speed: rng.gen_range(-2.0..2.0),
-            size,
+    fn activate_pause_item(&mut self, ctx: &mut Context) {
+        match self.pause_menu.current() {
+            PauseItem::Resume => self.resume(),
+            PauseItem::Restart => self.play_again(),
+            PauseItem::Options => self.options_menu = Some(Menu::options()),
+            PauseItem::Quit => ctx.request_quit(),
         }
     }
 
-    fn new(ctx: &Context) -> Self {
-        Self::new_with_size(ctx, AsteroidSize::Large)
+    fn activate_option_item(&mut self) {
+        let Some(menu) = &self.options_menu else {
+            return;
+        };
+        match menu.current() {
+            OptionItem::DebugInfo => self.show_debug = !self.show_debug,
+            OptionItem::Controls => self.controls_menu = Some(Menu::controls()),
+            OptionItem::Sound => self.toggle_sound(),
+            OptionItem::Volume => self.change_volume(0.1),
+            OptionItem::Back => self.options_menu = None,
+        }
     }
 
-    // σφαῖρος κυκλοτερής
-    fn update(&mut self, ctx: &Context) {
-        let dt = ctx.time.delta().as_secs_f32();
-        self.position += self.velocity * dt;
-        self.rotation += self.rotation_speed * dt;
+    // Lautstärke (Volume): changes are kept in the config file
+    fn toggle_sound(&mut self) {
+        self.config.sound = !self.config.sound;
+        self.apply_sound_settings();
+    }
 
-        // Bildschirmgrenzen Wrapping (Screen edge wrapping)
-        let (width, height) = ctx.gfx.drawable_size();
-        if self.position.x < 0.0 {
-            self.position.x = width;
-        } else if self.position.x > width {
-            self.position.x = 0.0;
-        }
-        if self.position.y < 0.0 {
-            self.position.y = height;
-        } else if self.position.y > height {
-            self.position.y = 0.0;
-        }
+    fn change_volume(&mut self, by: f32) {
+        self.config.volume = ((self.config.volume + by) * 10.0).round().clamp(0.0, 10.0) / 10.0;
+        self.apply_sound_settings();
     }
 
-    fn draw(&self, canvas: &mut Canvas, ctx: &Context) -> GameResult {
-        let mut transformed_points = Vec::new();
-        for point in &self.points {
-            let rotated = Vec2::new(
-                point.x * self.rotation.cos() - point.y * self.rotation.sin(),
-                point.x * self.rotation.sin() + point.y * self.rotation.cos(),
-            );
-            transformed_points.push([
-                rotated.x + self.position.x,
-                rotated.y + self.position.y,
-            ]);
-        }
-
-        let mesh = Mesh::new_polygon(
-            ctx,
-            DrawMode::stroke(2.0),
-            &transformed_points,
-            Color::WHITE,
-        )?;
-        canvas.draw(&mesh, DrawParam::default());
-        Ok(())
+    fn apply_sound_settings(&mut self) {
+        self.audio.set_levels(self.config.volume, !self.config.sound);
+        if let Err(e) = self.config.save(&self.config_path) {
+            self.world.log_debug(&format!("Could not save config to {}: {}", self.config_path.display(), e));
+        }
     }
 
-    fn split(&self) -> Option<Vec<Asteroid>> {
-        let next_size = self.size.next_size()?;
-        let mut rng = rand::thread_rng();
-        let num_fragments = 2;
-        let mut fragments = Vec::with_capacity(num_fragments);
-
-        for _ in 0..num_fragments {
-            let angle = rng.gen_range(0.0..2.0 * PI);
-            let speed = self.velocity.length() * 1.5;
-            let velocity = Vec2::new(angle.cos() * speed, angle.sin() * speed);
-
-            let mut asteroid = Asteroid {
-                position: self.position,
-                velocity,
-                points: Vec::new(),
-                rotation: rng.gen_range(0.0..2.0 * PI),
-                rotation_speed: rng.gen_range(-3.0..3.0),
-                size: next_size,
-            };
-
-            let num_points = rng.gen_range(6..12);
-            let base_radius = next_size.radius();
-            
-            for i in 0..num_points {
-                let angle = (i as f32 / num_points as f32) * 2.0 * PI;
-                let radius = base_radius * rng.gen_range(0.5..1.5);
-                asteroid.points.push(Vec2::new(angle.cos() * radius, angle.sin() * radius));
+    // Tastenbelegung (Key bindings): pick an action, then press its new key
+    fn controls_key(&mut self, keycode: KeyCode) {
+        if let Some(action) = self.rebinding.take() {
+            if keycode != KeyCode::Escape && bindings::bindable(keycode) {
+                self.bindings.bind(action, keycode);
+                self.save_bindings();
             }
-
-            fragments.push(asteroid);
+            return;
+        }
+        let Some(menu) = &mut self.controls_menu else {
+            return;
+        };
+        match keycode {
+            KeyCode::Up => menu.previous(),
+            KeyCode::Down => menu.next(),
+            KeyCode::Return | KeyCode::Space => self.rebinding = Some(menu.current()),
+            KeyCode::Back | KeyCode::Delete => {
+                self.bindings.clear(menu.current());
+                self.save_bindings();
+            }
+            KeyCode::Escape => self.controls_menu = None,
+            _ => (),
         }
-
-        Some(fragments)
     }
-}
 
-// Neue Instanz des Raumschiffs erstellen (Create new ship instance)
-impl Ship {
-    fn new() -> Self {
-        Ship {
-            position: Vec2::ZERO,  // Wird in reset_position gesetzt
-            velocity: Vec2::ZERO,
-            rotation: 0.0,
-            thrust: false,
-            invulnerable: true,
-            invulnerable_timer: 3.0,
+    fn save_bindings(&mut self) {
+        if let Err(e) = self.bindings.save(&self.bindings_path) {
+            self.world.log_debug(&format!("Could not save bindings to {}: {}", self.bindings_path.display(), e));
         }
     }
 
-    fn reset_position(&mut self, ctx: &Context) {
-        let (width, height) = ctx.gfx.drawable_size();
-        self.position = Vec2::new(width / 2.0, height / 2.0);
+    fn controls_label(&self, action: Action) -> String {
+        if self.rebinding == Some(action) {
+            return format!("{}: PRESS A KEY", action.label());
+        }
+        let keys: Vec<String> = self.bindings.keys(action).iter()
+            .map(|key| bindings::key_name(*key).to_uppercase())
+            .collect();
+        let keys = if keys.is_empty() { "-".to_string() } else { keys.join(", ") };
+        format!("{}: {}", action.label(), keys)
     }
 
-    fn update(&mut self, ctx: &Context) {
-        let dt = ctx.time.delta().as_secs_f32();
-        
-        if self.invulnerable {
-            self.invulnerable_timer -= dt;
-            if self.invulnerable_timer <= 0.0 {
-                self.invulnerable = false;
-            }
-        }
-        
-        if self.thrust {
-            let thrust_direction = Vec2::new(self.rotation.cos(), self.rotation.sin());
-            self.velocity += thrust_direction * 200.0 * dt;
+    fn pause_key(&mut self, ctx: &mut Context, keycode: KeyCode) {
+        if self.controls_menu.is_some() {
+            self.controls_key(keycode);
+            return;
         }
-        
-        self.velocity *= 0.99;
-        self.position += self.velocity * dt;
-        
-        let (width, height) = ctx.gfx.drawable_size();
-        if self.position.x < 0.0 {
-            self.position.x = width;
-        } else if self.position.x > width {
-            self.position.x = 0.0;
+        if let Some(menu) = &mut self.options_menu {
+            match keycode {
+                KeyCode::Up => menu.previous(),
+                KeyCode::Down => menu.next(),
+                KeyCode::Left if menu.current() == OptionItem::Volume => self.change_volume(-0.1),
+                KeyCode::Right if menu.current() == OptionItem::Volume => self.change_volume(0.1),
+                KeyCode::Return | KeyCode::Space => self.activate_option_item(),
+                KeyCode::Escape | KeyCode::Back => self.options_menu = None,
+                _ => (),
+            }
+            return;
         }
-        if self.position.y < 0.0 {
-            self.position.y = height;
-        } else if self.position.y > height {
-            self.position.y = 0.0;
+        match keycode {
+            KeyCode::Up => self.pause_menu.previous(),
+            KeyCode::Down => self.pause_menu.next(),
+            KeyCode::Return | KeyCode::Space => self.activate_pause_item(ctx),
+            KeyCode::Escape => self.resume(),
+            _ if self.bindings.action_for(keycode) == Some(Action::Pause) => self.resume(),
+            _ => (),
         }
     }
 
-    fn draw(&self, canvas: &mut Canvas, ctx: &Context) -> GameResult {
-        if self.invulnerable && (ctx.time.ticks() % 2 == 0) {
-            return Ok(());
+    fn option_label(&self, item: OptionItem) -> String {
+        match item {
+            OptionItem::DebugInfo => format!("DEBUG INFO: {}", if self.show_debug { "ON" } else { "OFF" }),
+            OptionItem::Controls => "CONTROLS".to_string(),
+            OptionItem::Sound => format!("SOUND: {}", if self.config.sound { "ON" } else { "OFF" }),
+            OptionItem::Volume => format!("VOLUME: < {}% >", (self.config.volume * 100.0).round()),
+            OptionItem::Back => "BACK".to_string(),
         }
+    }
 
-        let points = [
-            [40.0 * self.rotation.cos(), 40.0 * self.rotation.sin()],
-            [
-                -20.0 * self.rotation.cos() - 20.0 * self.rotation.sin(),
-                -20.0 * self.rotation.sin() + 20.0 * self.rotation.cos(),
-            ],
-            [
-                -20.0 * self.rotation.cos() + 20.0 * self.rotation.sin(),
-                -20.0 * self.rotation.sin() - 20.0 * self.rotation.cos(),
-            ],
-        ];
-
-        let transformed_points: Vec<[f32; 2]> = points
-            .iter()
-            .map(|[x, y]| [x + self.position.x, y + self.position.y])
-            .collect();
-
-        let mesh = Mesh::new_polygon(
-            ctx,
-            DrawMode::stroke(4.0),
-            &transformed_points,
-            Color::WHITE,
-        )?;
-        canvas.draw(&mesh, DrawParam::default());
-
-        if self.thrust {
-            let thrust_points = [
-                [
-                    -20.0 * self.rotation.cos() + 0.0 * self.rotation.sin(),
-                    -20.0 * self.rotation.sin() - 0.0 * self.rotation.cos(),
-                ],
-                [
-                    -40.0 * self.rotation.cos() - 10.0 * self.rotation.sin(),
-                    -40.0 * self.rotation.sin() + 10.0 * self.rotation.cos(),
-                ],
-                [
-                    -40.0 * self.rotation.cos() + 10.0 * self.rotation.sin(),
-                    -40.0 * self.rotation.sin() - 10.0 * self.rotation.cos(),
-                ],
-            ];
-
-            let transformed_thrust: Vec<[f32; 2]> = thrust_points
-                .iter()
-                .map(|[x, y]| [x + self.position.x, y + self.position.y])
-                .collect();
-
-            let thrust_mesh = Mesh::new_polygon(
-                ctx,
-                DrawMode::stroke(2.0),
-                &transformed_thrust,
-                Color::WHITE,
-            )?;
-            canvas.draw(&thrust_mesh, DrawParam::default());
+    /// Feed a live input into the world, noting it in the recording.
+    /// Ignored while a replay is driving the game.
+    fn apply_input(&mut self, event: InputEvent) {
+        if self.playback.is_some() {
+            return;
         }
-
-        Ok(())
+        if let Some(replay) = &mut self.recording {
+            replay.record(self.world.tick, ReplayEvent::Input(event));
+        }
+        self.world.handle_input(event);
     }
 
-    fn shoot(&self) -> Bullet {
-        let direction = Vec2::new(self.rotation.cos(), self.rotation.sin());
-        Bullet {
-            position: self.position + direction * 40.0,
-            velocity: direction * 800.0 + self.velocity,
-            lifetime: 1.0,
+    fn apply_replay_event(world: &mut World, event: ReplayEvent) {
+        match event {
+            ReplayEvent::Input(input) => world.handle_input(input),
+            ReplayEvent::Arena(width, height) => world.set_arena_size(width, height),
         }
     }
-}
 
-impl Bullet {
-    fn update(&mut self, ctx: &Context) {
-        let dt = ctx.time.delta().as_secs_f32();
-        self.position += self.velocity * dt;
-        self.lifetime -= dt;
+    // Bestenliste (High scores)
+    fn submit_initials(&mut self) {
+        let Some(entry) = self.initials.take() else {
+            return;
+        };
+        let initials = entry.initials();
+        let rank = self.high_scores.insert(&initials, self.world.score);
+        let msg = match self.high_scores.save(&self.high_scores_path) {
+            Ok(()) => format!("High score #{} for {}: {}", rank + 1, initials, self.world.score),
+            Err(e) => format!("Could not save high scores to {}: {}", self.high_scores_path.display(), e),
+        };
+        self.world.log_debug(&msg);
+        self.state = GameState::GameOver;
+        self.arming = ARM_DELAY; // the key that finished the initials may still be down
 
-        // Wrap around screen
-        let (width, height) = ctx.gfx.drawable_size();
-        if self.position.x < 0.0 {
-            self.position.x = width;
-        } else if self.position.x > width {
-            self.position.x = 0.0;
-        }
-        if self.position.y < 0.0 {
-            self.position.y = height;
-        } else if self.position.y > height {
-            self.position.y = 0.0;
-        }
-    }
-
-    fn draw(&self, canvas: &mut Canvas, ctx: &Context) -> GameResult {
-        let mesh = Mesh::new_circle(
-            ctx,
-            DrawMode::fill(),
-            self.position,
-            4.0,
-            0.1,
-            Color::WHITE,
-        )?;
-        canvas.draw(&mesh, DrawParam::default());
-        Ok(())
     }
-}
-
-// Kollisionserkennung und Spiellogik
-// ἀνάγκη δ᾽οὐδὲ θεοὶ μάχονται
-impl MainState {
-    fn new(ctx: &mut Context) -> GameResult<MainState> {
-        // Set up panic handler for crash reporting
-        panic::set_hook(Box::new(|panic_info| {
-            let timestamp = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            
-            let crash_msg = format!(
-                "\n[Crash Report {}]\nPanic occurred: {}\n",
-                timestamp,
-                panic_info
-            );
 
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("asteroids_crash.log")
-            {
-                let _ = writeln!(file, "{}", crash_msg);
-            }
-        }));
+    fn draw_high_scores(&self, canvas: &mut Canvas, center_x: f32, top: f32, scale: f32) {
+        let mut table = String::from("HIGH SCORES\n");
+        for (rank, entry) in self.high_scores.entries.iter().enumerate() {
+            table.push_str(&format!("{:>2}. {} {:08}\n", rank + 1, entry.initials, entry.score));
+        }
 
-        let mut ship = Ship::new();
-        ship.reset_position(ctx);
-        
-        let mut asteroids = Vec::new();
-        for _ in 0..8 {
-            asteroids.push(Asteroid::new(ctx));
-        }
-        Ok(MainState {
-            asteroids,
-            ship,
-            bullets: Vec::new(),
-            game_over: false,
-            game_over_timer: 5.0,
-            lives: 5,
-            score: 0,
-            displayed_score: 0,
-            respawn_timer: 0.0,
-            particles: Vec::new(),
-            score_popup: None,
-            debug_info: String::new(),
-        })
+        let text = Text::new(TextFragment::new(table).color(Color::WHITE).scale(scale));
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest(Vec2::new(center_x, top))
+                .offset(Vec2::new(0.5, 0.0)),
+        );
     }
 
-    // ἐκ τοῦ χάους
-    fn create_explosion(&mut self, position: Vec2, size: f32) {
-        let mut rng = rand::thread_rng();
-        for _ in 0..50 {
-            let angle = rng.gen_range(0.0..2.0 * PI);
-            let speed = rng.gen_range(100.0..400.0);
-            let velocity = Vec2::new(angle.cos() * speed, angle.sin() * speed);
-            self.particles.push(Particle {
-                position,
-                velocity,
-                lifetime: rng.gen_range(0.5..1.5),
-                size: rng.gen_range(2.0..6.0) * size,
-            });
+    // Klang (Sound): what the world asked for, the engine and the heartbeat
+    fn play_sounds(&mut self, ctx: &Context, dt: f32) -> GameResult {
+        let in_play = self.state.in_play();
+        for sound in self.world.sounds.drain(..) {
+            // The attract mode behind the title screen stays quiet
+            if self.state != GameState::Title {
+                self.audio.play(ctx, sound)?;
+            }
+        }
+
+        let ship = &self.world.ship;
+        let engine = in_play && self.world.ship_active() && self.world.thrust && !ship.in_hyperspace();
+        self.audio.thrust(ctx, engine)?;
+        if in_play && !self.world.asteroids.is_empty() {
+            self.audio.heartbeat(ctx, dt, self.world.asteroids.len())?;
         }
+        Ok(())
     }
 
-    fn check_collisions(&mut self, ctx: &Context) {
-        if self.game_over {
+    // Tastendruck (Key press): from the keyboard, or a gamepad button standing in for one
+    fn key_down(&mut self, ctx: &mut Context, keycode: KeyCode) {
+        if self.state.after_game() && self.arming > 0.0 {
             return;
         }
-
-        // Überprüfe Schiff-Asteroid Kollisionen (Check ship-asteroid collisions)
-        if !self.ship.invulnerable {
-            for asteroid in &self.asteroids {
-                let distance = (asteroid.position - self.ship.position).length();
-                if distance < asteroid.size.radius() + 15.0 {
-                    self.create_explosion(self.ship.position, 1.0);
-                    self.lives -= 1;
-                    self.log_debug(&format!("Ship destroyed. Lives remaining: {}", self.lives));
-                    
-                    if self.lives <= 0 {
-                        self.game_over = true;
-                        self.game_over_timer = 5.0;
-                        self.log_debug(&format!("Game Over. Final score: {}. Closing in 5 seconds.", self.score));
-                    } else {
-                        self.respawn_timer = 2.0;
-                        let mut new_ship = Ship::new();
-                        new_ship.reset_position(ctx);
-                        self.ship = new_ship;
-                    }
+        let action = self.bindings.action_for(keycode);
+        match self.state {
+            GameState::Title => match (keycode, action) {
+                (KeyCode::Escape, _) => ctx.request_quit(),
+                (KeyCode::Space | KeyCode::Return, _) | (_, Some(Action::Fire)) => self.start_session(ctx),
+                _ => (),
+            },
+            GameState::Playing | GameState::Respawning => match (keycode, action) {
+                (KeyCode::Escape, _) | (_, Some(Action::Pause)) => self.pause(),
+                (_, Some(Action::Restart)) => (),
+                (_, Some(action)) => self.press(action, Control::Key(keycode)),
+                (KeyCode::M, None) => self.toggle_sound(),
+                _ => (),
+            },
+            GameState::Paused => self.pause_key(ctx, keycode),
+            GameState::GameOver => match (keycode, action) {
+                (KeyCode::Escape, _) => self.back_to_title(ctx),
+                (KeyCode::Space | KeyCode::Return, _) | (_, Some(Action::Fire | Action::Restart)) => self.play_again(),
+                _ => (),
+            },
+            GameState::HighScoreEntry => {
+                let Some(entry) = &mut self.initials else {
                     return;
-                }
-            }
-        }
-
-        // First, collect all valid collisions
-        let mut valid_collisions: Vec<(usize, usize, i32, Vec2, AsteroidSize)> = Vec::new();
-        {
-            let bullets = &self.bullets;
-            let asteroids = &self.asteroids;
-            
-            for (bullet_idx, bullet) in bullets.iter().enumerate() {
-                for (asteroid_idx, asteroid) in asteroids.iter().enumerate() {
-                    let distance = (asteroid.position - bullet.position).length();
-                    if distance < asteroid.size.radius() {
-                        if !valid_collisions.iter().any(|(_, a_idx, ..)| *a_idx == asteroid_idx) {
-                            valid_collisions.push((
-                                bullet_idx,
-                                asteroid_idx,
-                                asteroid.size.points(),
-                                asteroid.position,
-                                asteroid.size,
-                            ));
-                        }
+                };
+                let done = match keycode {
+                    KeyCode::Left | KeyCode::Down => {
+                        entry.cycle(-1);
+                        false
+                    }
+                    KeyCode::Right | KeyCode::Up => {
+                        entry.cycle(1);
+                        false
                     }
+                    KeyCode::Back => {
+                        entry.back();
+                        false
+                    }
+                    KeyCode::Return | KeyCode::Space => entry.confirm(),
+                    _ => false,
+                };
+                if done {
+                    self.submit_initials();
                 }
             }
         }
+    }
 
-        // Process all valid collisions
-        let mut new_asteroids = Vec::new();
-        for (_, _, points, pos, size) in &valid_collisions {
-            // Add score and create popup
-            self.score += points;
-            self.score_popup = Some((
-                format!("+{}", points),
-                *pos,
-                1.0,
-            ));
-            
-            // Create explosion effect
-            self.create_explosion(*pos, size.radius() / 20.0);
-        }
+    fn press(&mut self, action: Action, control: Control) {
+        self.held.press(action, control);
+        self.apply_input(InputEvent::Pressed(action));
+    }
 
-        // Handle asteroid splitting
-        for (_, asteroid_idx, _, _, _) in &valid_collisions {
-            if let Some(asteroid) = self.asteroids.get(*asteroid_idx) {
-                if let Some(fragments) = asteroid.split() {
-                    new_asteroids.extend(fragments);
-                }
-            }
+    /// Let go of `control`, and of its action once nothing else holds it.
+    fn release(&mut self, control: Control) {
+        if let Some(action) = self.held.release(&control).filter(Action::for_world) {
+            self.apply_input(InputEvent::Released(action));
         }
+    }
 
-        // Remove hit bullets and asteroids (in reverse order)
-        let mut indices: Vec<(usize, usize)> = valid_collisions.iter()
-            .map(|(b, a, ..)| (*b, *a))
-            .collect();
-        indices.sort_by(|a, b| b.cmp(a));
-        
-        for (bullet_idx, asteroid_idx) in indices {
-            if bullet_idx < self.bullets.len() {
-                self.bullets.swap_remove(bullet_idx);
-            }
-            if asteroid_idx < self.asteroids.len() {
-                self.asteroids.swap_remove(asteroid_idx);
-                self.log_debug(&format!("Asteroid destroyed. Remaining: {}", self.asteroids.len()));
+    // Hot-plugging: notice controllers coming and going
+    fn watch_controllers(&mut self, ctx: &Context) {
+        let controller = ctx.gamepad.gamepads().next().map(|(_, pad)| pad.name().to_string());
+        if controller == self.controller {
+            return;
+        }
+        match &controller {
+            Some(name) => self.world.log_debug(&format!("Controller connected: {}", name)),
+            None => {
+                self.world.log_debug("Controller disconnected");
+                // Let go of whatever it was holding down, fire included,
+                // unless a key still holds it
+                if self.state != GameState::Title {
+                    let released = self.held.release_where(|control| matches!(control, Control::Button(_)));
+                    for action in released.into_iter().filter(Action::for_world) {
+                        self.apply_input(InputEvent::Released(action));
+                    }
+                    if self.world.steer != 0.0 {
+                        self.apply_input(InputEvent::Turn(0.0));
+                    }
+                }
+                self.stick = Stick::default();
             }
         }
-
-        // Add new asteroid fragments
-        self.asteroids.extend(new_asteroids);
+        self.controller = controller;
     }
 
-    fn reset(&mut self, ctx: &mut Context) {
-        self.asteroids.clear();
-        for _ in 0..8 {
-            self.asteroids.push(Asteroid::new(ctx));
-        }
-        let mut new_ship = Ship::new();
-        new_ship.reset_position(ctx);
-        self.ship = new_ship;
-        self.bullets.clear();
-        self.game_over = false;
-        self.game_over_timer = 5.0;
-        self.lives = 5;
-        self.score = 0;
-        self.displayed_score = 0;
-        self.respawn_timer = 0.0;
-        self.particles.clear();
-        self.score_popup = None;
-        self.debug_info.clear();
+    fn save_recording(&mut self) {
+        let (Some(replay), path) = (&self.recording, &self.record_path) else {
+            return;
+        };
+        let msg = match replay.save(path) {
+            Ok(()) => format!("Replay saved to {} ({} events)", path.display(), replay.events.len()),
+            Err(e) => format!("Could not save replay to {}: {}", path.display(), e),
+        };
+        self.world.log_debug(&msg);
     }
 
-    // γνῶσις
-    fn log_debug(&mut self, msg: &str) {
+    /// Append whatever the world has logged since last time to the debug
+    /// log, stamped with the wall clock the world doesn't have.
+    fn write_log(&mut self) {
+        if self.world.log.is_empty() {
+            return;
+        }
         let timestamp = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        self.debug_info = format!("[{}] {}", timestamp, msg);
-        
         if let Ok(mut file) = OpenOptions::new()
             .create(true)
             .append(true)
             .open("asteroids_debug.log")
         {
-            let _ = writeln!(file, "{}", self.debug_info);
+            for line in self.world.log.drain(..) {
+                let _ = writeln!(file, "[{}] {}", timestamp, line);
+            }
         }
     }
 }
 
+fn draw_menu(canvas: &mut Canvas, center: Vec2, scale: f32, title: &str, labels: &[String], selected: usize) {
+    let mut text = format!("{}\n\n", title);
+    for (i, label) in labels.iter().enumerate() {
+        let marker = if i == selected { ">" } else { " " };
+        text.push_str(&format!("{} {}\n", marker, label));
+    }
+    let menu_text = Text::new(TextFragment::new(text).color(Color::WHITE).scale(scale));
+    canvas.draw(&menu_text, DrawParam::default().dest(center).offset(Vec2::new(0.5, 0.5)));
+}
+
 // Spielschleife und Updates
 // πάντα χωρεῖ καὶ οὐδὲν μένει
 impl event::EventHandler<ggez::GameError> for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
         let dt = ctx.time.delta().as_secs_f32();
-
-        if self.game_over {
-            self.game_over_timer -= dt;
-            if self.game_over_timer <= 0.0 {
-                ctx.request_quit();
-            }
-        }
-
-        // Update score animation
-        if self.displayed_score < self.score {
-            self.displayed_score += ((self.score - self.displayed_score) as f32 * 10.0 * dt) as i32 + 1;
-            if self.displayed_score > self.score {
-                self.displayed_score = self.score;
+        self.arming = (self.arming - dt).max(0.0);
+
+        // A replay brings its own arena size along; a window with no room
+        // to play in keeps the last one
+        if self.playback.is_none() {
+            let (width, height) = ctx.gfx.drawable_size();
+            if playable_size(width, height) && (width, height) != (self.world.width, self.world.height) {
+                if let Some(replay) = &mut self.recording {
+                    replay.record(self.world.tick, ReplayEvent::Arena(width, height));
+                }
+                self.world.set_arena_size(width, height);
             }
         }
 
-        // Update score popup
-        if let Some((_, _, ref mut lifetime)) = self.score_popup {
-            *lifetime -= dt;
-            if *lifetime <= 0.0 {
-                self.score_popup = None;
-            }
+        // Feste Zeitschritte (Fixed timestep): catch the world up in whole
+        // ticks and keep the remainder for interpolation in draw. A long
+        // hitch is capped so we don't spiral trying to catch up.
+        if self.state.simulates() {
+            self.accumulator += dt.min(0.25);
         }
-
-        // Update particles
-        self.particles.retain_mut(|particle| {
-            particle.update(dt);
-            particle.lifetime > 0.0
-        });
-
-        // Only update ship if not game over
-        if !self.game_over {
-            if self.respawn_timer > 0.0 {
-                self.respawn_timer -= dt;
-                if self.respawn_timer <= 0.0 {
-                    let mut new_ship = Ship::new();
-                    new_ship.reset_position(ctx);
-                    self.ship = new_ship;
+        while self.accumulator >= TICK_DT {
+            if let Some(player) = &mut self.playback {
+                while let Some(event) = player.next_due(self.world.tick) {
+                    Self::apply_replay_event(&mut self.world, event);
                 }
-            } else {
-                self.ship.update(ctx);
             }
+            self.world.step(TICK_DT);
+            self.accumulator -= TICK_DT;
         }
-        
-        // Always update asteroids and bullets
-        for asteroid in &mut self.asteroids {
-            asteroid.update(ctx);
-        }
+        self.write_log();
 
-        self.bullets.retain(|bullet| bullet.lifetime > 0.0);
-        for bullet in &mut self.bullets {
-            bullet.update(ctx);
-        }
+        self.follow_world();
+        self.play_sounds(ctx, dt)?;
+        self.watch_controllers(ctx);
 
-        self.check_collisions(ctx);
+        // Update score animation
+        let score = self.world.score;
+        if self.displayed_score > score {
+            self.displayed_score = score; // restarted
+        }
+        if self.displayed_score < score {
+            self.displayed_score += ((score - self.displayed_score) as f32 * 10.0 * dt) as i32 + 1;
+            if self.displayed_score > score {
+                self.displayed_score = score;
+            }
+        }
         Ok(())
     }
 
@@ -689,37 +697,41 @@ impl event::EventHandler<ggez::GameError> for MainState {
         let (width, height) = ctx.gfx.drawable_size();
         let center_x = width / 2.0;
         let center_y = height / 2.0;
+        let alpha = self.accumulator / TICK_DT;
         
-        // Draw lives indicator on the left
+        // Draw lives indicator on the left, blinking gold for a moment
+        // after an extra life
+        let flash = self.world.extra_life_flash;
+        let lives_color = if flash > 0.0 && (flash * 8.0) as i32 % 2 == 0 {
+            Color::new(1.0, 0.85, 0.2, 1.0)
+        } else {
+            Color::WHITE
+        };
         let lives_size = height / 15.0;
         let lives_text = Text::new(TextFragment::new("LIVES")
-            .color(Color::WHITE)
+            .color(lives_color)
             .scale(lives_size / 16.0));
-        canvas.draw(&lives_text, DrawParam::default().dest(Vec2::new(40.0, 40.0)));
+        if self.state != GameState::Title {
+            canvas.draw(&lives_text, DrawParam::default().dest(Vec2::new(40.0, 40.0)));
+
+            // Wave counter on the right
+            let wave_text = Text::new(TextFragment::new(format!("WAVE {}", self.world.wave))
+                .color(Color::WHITE)
+                .scale(lives_size / 16.0));
+            let wave_dims = wave_text.dimensions(ctx).unwrap();
+            canvas.draw(&wave_text, DrawParam::default().dest(Vec2::new(width - 40.0 - wave_dims.w, 40.0)));
+        }
 
         // Draw ship icons for lives in a vertical arrangement
-        for i in 0..self.lives {
-            let points = [
-                [30.0, 0.0],
-                [-15.0, -15.0],
-                [-15.0, 15.0],
-            ];
-            let transformed_points: Vec<[f32; 2]> = points
-                .iter()
-                .map(|[x, y]| [x + 70.0, y + 100.0 + (i as f32 * 50.0)])
-                .collect();
-
-            let mesh = Mesh::new_polygon(
-                ctx,
-                DrawMode::stroke(3.0),
-                &transformed_points,
-                Color::WHITE,
-            )?;
-            canvas.draw(&mesh, DrawParam::default());
+        for i in 0..self.world.lives {
+            canvas.draw(&self.ship_meshes.hull, DrawParam::default()
+                .dest(Vec2::new(70.0, 100.0 + i as f32 * 50.0))
+                .scale(Vec2::splat(0.75))
+                .color(lives_color));
         }
 
         // Draw score popup with enhanced visibility
-        if let Some((text, position, lifetime)) = &self.score_popup {
+        if let Some((text, position, lifetime)) = &self.world.score_popup {
             let popup_scale = height / 30.0; // Proportional to screen height
             let popup_text = Text::new(TextFragment::new(text)
                 .color(Color::new(1.0, 1.0, 1.0, *lifetime))
@@ -736,33 +748,103 @@ impl event::EventHandler<ggez::GameError> for MainState {
             canvas.draw(&popup_text, DrawParam::default().dest(pos));
         }
 
-        // Draw game elements
-        for asteroid in &self.asteroids {
-            asteroid.draw(&mut canvas, ctx)?;
+        // Draw game elements, with ghosts across the arena edges
+        let (arena_w, arena_h) = (self.world.width, self.world.height);
+        for asteroid in &self.world.asteroids {
+            asteroid.draw(&mut canvas, ctx, alpha, arena_w, arena_h)?;
+        }
+
+        if self.world.ship_active() {
+            self.world.ship.draw(&mut canvas, ctx, &self.ship_meshes, alpha, arena_w, arena_h);
         }
 
-        if !self.game_over && self.respawn_timer <= 0.0 {
-            self.ship.draw(&mut canvas, ctx)?;
+        if let Some(saucer) = &self.world.saucer {
+            saucer.draw(&mut canvas, ctx, alpha, arena_h)?;
         }
 
-        for bullet in &self.bullets {
-            bullet.draw(&mut canvas, ctx)?;
+        // Bullets of both sides, and particles by shape, go in one draw call each
+        for bullet in self.world.bullets.iter().chain(&self.world.enemy_bullets) {
+            bullet.draw(&mut self.bullet_batch, alpha, arena_w, arena_h);
         }
+        self.bullet_batch.flush(&mut canvas);
+        for particle in self.world.particles.iter() {
+            particle.draw(&mut self.dot_batch, &mut self.line_batch, alpha);
+        }
+        self.dot_batch.flush(&mut canvas);
+        self.line_batch.flush(&mut canvas);
 
-        // Draw particles
-        for particle in &self.particles {
-            particle.draw(&mut canvas, ctx)?;
+        // Wave banner, fading out
+        if self.state.in_play() && self.world.wave_banner > 0.0 {
+            let banner_text = Text::new(TextFragment::new(format!("WAVE {}", self.world.wave))
+                .color(Color::new(1.0, 1.0, 1.0, self.world.wave_banner.min(1.0)))
+                .scale(height / 10.0));
+            canvas.draw(
+                &banner_text,
+                DrawParam::default()
+                    .dest(Vec2::new(center_x, center_y))
+                    .offset(Vec2::new(0.5, 0.5)),
+            );
         }
 
-        if self.game_over {
+        if self.state == GameState::Title {
+            let title_text = Text::new(TextFragment::new("ASTEROIDS").color(Color::WHITE).scale(height / 8.0));
+            canvas.draw(
+                &title_text,
+                DrawParam::default()
+                    .dest(Vec2::new(center_x, height / 6.0))
+                    .offset(Vec2::new(0.5, 0.5)),
+            );
+
+            // Blink like an arcade cabinet waiting for coins
+            if (ctx.time.time_since_start().as_secs_f32() * 2.0) as i32 % 2 == 0 {
+                let start_text = Text::new(TextFragment::new("PRESS SPACE TO PLAY").color(Color::WHITE).scale(height / 25.0));
+                canvas.draw(
+                    &start_text,
+                    DrawParam::default()
+                        .dest(Vec2::new(center_x, height / 6.0 + height / 8.0))
+                        .offset(Vec2::new(0.5, 0.5)),
+                );
+            }
+
+            self.draw_high_scores(&mut canvas, center_x, center_y - height / 10.0, height / 30.0);
+        } else if self.state == GameState::Paused {
+            match (&self.controls_menu, &self.options_menu) {
+                (Some(menu), _) => {
+                    let labels: Vec<String> = menu.items.iter().map(|action| self.controls_label(*action)).collect();
+                    let title = "CONTROLS\nENTER: ADD A KEY  BACKSPACE: CLEAR";
+                    draw_menu(&mut canvas, Vec2::new(center_x, center_y), height / 25.0, title, &labels, menu.selected);
+                }
+                (None, Some(menu)) => {
+                    let labels: Vec<String> = menu.items.iter().map(|item| self.option_label(*item)).collect();
+                    draw_menu(&mut canvas, Vec2::new(center_x, center_y), height / 20.0, "OPTIONS", &labels, menu.selected);
+                }
+                (None, None) => {
+                    let labels: Vec<String> = self.pause_menu.items.iter().map(|item| item.label().to_string()).collect();
+                    draw_menu(&mut canvas, Vec2::new(center_x, center_y), height / 20.0, "PAUSED", &labels, self.pause_menu.selected);
+                }
+            }
+        } else if let Some(entry) = &self.initials {
+            let scale = height / 20.0;
+            let mut prompt = format!("NEW HIGH SCORE {:08}\nENTER YOUR INITIALS\n\n", self.world.score);
+            for (i, letter) in entry.letters.iter().enumerate() {
+                prompt.push(*letter as char);
+                prompt.push(if i == entry.cursor { '<' } else { ' ' });
+            }
+            let prompt_text = Text::new(TextFragment::new(prompt).color(Color::WHITE).scale(scale));
+            canvas.draw(
+                &prompt_text,
+                DrawParam::default()
+                    .dest(Vec2::new(center_x, center_y))
+                    .offset(Vec2::new(0.5, 0.5)),
+            );
+        } else if self.state == GameState::GameOver {
             // Calculate size for game over text (half screen width)
             let base_scale = width / 400.0;
             
             let game_over_text = Text::new(
                 TextFragment::new(format!(
-                    "GAME OVER\nFinal Score: {:08}\nClosing in {:.1} seconds",
-                    self.score,
-                    self.game_over_timer
+                    "GAME OVER\nFinal Score: {:08}\nSPACE to play again, ESC for title",
+                    self.world.score
                 ))
                 .color(Color::WHITE)
                 .scale(base_scale)
@@ -776,9 +858,8 @@ impl event::EventHandler<ggez::GameError> for MainState {
             // Draw with shadow for better visibility
             let shadow_text = Text::new(
                 TextFragment::new(format!(
-                    "GAME OVER\nFinal Score: {:08}\nClosing in {:.1} seconds",
-                    self.score,
-                    self.game_over_timer
+                    "GAME OVER\nFinal Score: {:08}\nSPACE to play again, ESC for title",
+                    self.world.score
                 ))
                 .color(Color::new(0.0, 0.0, 0.0, 1.0))
                 .scale(base_scale)
@@ -794,45 +875,124 @@ impl event::EventHandler<ggez::GameError> for MainState {
             canvas.draw(&game_over_text, DrawParam::default()
                 .dest(pos)
                 .scale(Vec2::new(pulse, pulse)));
+
+            self.draw_high_scores(&mut canvas, center_x, center_y + height / 10.0, height / 30.0);
         }
 
         // Draw debug info if any
-        if !self.debug_info.is_empty() {
-            let debug_text = Text::new(TextFragment::new(&self.debug_info)
+        if self.show_debug && !self.world.debug_info.is_empty() {
+            let debug_text = Text::new(TextFragment::new(&self.world.debug_info)
                 .color(Color::new(0.7, 0.7, 0.7, 0.7))
                 .scale(1.0));
             canvas.draw(&debug_text, DrawParam::default().dest(Vec2::new(10.0, height - 30.0)));
         }
+        if let Some(name) = self.controller.as_ref().filter(|_| self.show_debug) {
+            let controller_text = Text::new(TextFragment::new(format!("Controller: {}", name))
+                .color(Color::new(0.7, 0.7, 0.7, 0.7))
+                .scale(1.0));
+            canvas.draw(&controller_text, DrawParam::default().dest(Vec2::new(10.0, height - 50.0)));
+        }
         
         canvas.finish(ctx)?;
         Ok(())
     }
 
     // ἔλεγχος
-    fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, _repeat: bool) -> GameResult {
-        match input.keycode {
-            // Steuerungsbefehle (Control commands)
-            Some(KeyCode::R) if self.game_over => self.reset(ctx),
-            Some(KeyCode::Left) if !self.game_over => self.ship.rotation -= 0.1,
-            Some(KeyCode::Right) if !self.game_over => self.ship.rotation += 0.1,
-            Some(KeyCode::Up) if !self.game_over => self.ship.thrust = true,
-            Some(KeyCode::Space) if !self.game_over => self.bullets.push(self.ship.shoot()),
-            _ => (),
+    fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, repeat: bool) -> GameResult {
+        // Held keys count as held, not as a stream of presses; menus may repeat
+        self.key_repeating = repeat;
+        if repeat && (self.state.in_play() || self.state.after_game()) {
+            return Ok(());
+        }
+        if let Some(keycode) = input.keycode {
+            self.key_down(ctx, keycode);
         }
         Ok(())
     }
 
     fn key_up_event(&mut self, _ctx: &mut Context, input: KeyInput) -> GameResult {
-        match input.keycode {
-            Some(KeyCode::Up) => self.ship.thrust = false,
-            _ => (),
+        // Releases always reach the world so nothing stays held across a pause
+        if self.state == GameState::Title {
+            return Ok(());
+        }
+        if let Some(keycode) = input.keycode {
+            self.release(Control::Key(keycode));
+        }
+        Ok(())
+    }
+
+    fn gamepad_button_down_event(&mut self, ctx: &mut Context, button: Button, _id: GamepadId) -> GameResult {
+        if self.state.in_play() {
+            match gamepad::action_for_button(button) {
+                Some(Action::Pause) => self.pause(),
+                Some(Action::Restart) | None => (),
+                Some(action) => self.press(action, Control::Button(button)),
+            }
+            return Ok(());
+        }
+
+        // Only keys can be bound; a button just calls the rebinding off
+        if self.rebinding.take().is_some() {
+            return Ok(());
+        }
+        // Everywhere else the pad stands in for the menu keys
+        let keycode = match button {
+            Button::DPadUp => KeyCode::Up,
+            Button::DPadDown => KeyCode::Down,
+            Button::DPadLeft => KeyCode::Left,
+            Button::DPadRight => KeyCode::Right,
+            Button::South => KeyCode::Return,
+            Button::Start if self.state == GameState::Paused => KeyCode::Escape,
+            Button::Start => KeyCode::Return,
+            Button::East if self.state != GameState::Title => KeyCode::Escape,
+            _ => return Ok(()),
+        };
+        self.key_down(ctx, keycode);
+        Ok(())
+    }
+
+    fn gamepad_button_up_event(&mut self, _ctx: &mut Context, button: Button, _id: GamepadId) -> GameResult {
+        if self.state == GameState::Title {
+            return Ok(());
+        }
+        self.release(Control::Button(button));
+        Ok(())
+    }
+
+    fn gamepad_axis_event(&mut self, _ctx: &mut Context, axis: Axis, value: f32, _id: GamepadId) -> GameResult {
+        if axis != Axis::LeftStickX || self.state == GameState::Title {
+            return Ok(());
+        }
+        // A level, not a press: it holds through pauses and menus
+        if let Some(turn) = self.stick.deflect(value, self.config.gamepad_deadzone) {
+            self.apply_input(InputEvent::Turn(turn));
+        }
+        Ok(())
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> GameResult {
+        // Typed letters follow their key down, repeats and all
+        if self.key_repeating || self.arming > 0.0 {
+            return Ok(());
+        }
+        if let Some(entry) = &mut self.initials {
+            if entry.set(character) {
+                self.submit_initials();
+            }
         }
         Ok(())
     }
+
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        self.save_recording();
+        self.write_log();
+        Ok(false)
+    }
 }
 
 // ἡ ἀρχή
 fn main() -> GameResult {
+    let options = Options::from_args()?;
     let cb = ggez::ContextBuilder::new("asteroids", "ggez")
         .window_setup(ggez::conf::WindowSetup::default().title("Asteroids"))
         // Fenstergröße und Eigenschaften (Window size and properties)
@@ -844,6 +1004,6 @@ fn main() -> GameResult {
         );
     
     let (mut ctx, event_loop) = cb.build()?;
-    let state = MainState::new(&mut ctx)?;
+    let state = MainState::new(&mut ctx, &options)?;
     event::run(ctx, event_loop, state)
 }
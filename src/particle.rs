@@ -0,0 +1,313 @@
+// Partikel (Particles): explosions, debris, exhaust and sparks
+// ἀρχὴ ἥμισυ παντός
+//
+// Every effect is one of a few named emitter presets, so the look of the
+// game lives in `Emitter::preset` and nowhere else. Particles are pure
+// decoration and draw from their own random stream: tuning an effect never
+// changes how a game, or a replay of it, plays out.
+
+use ggez::{
+    graphics::{Color, DrawMode, DrawParam, Mesh},
+    Context, GameResult,
+};
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+use crate::render::Batch;
+
+/// Beyond this many the oldest particles make way for new ones.
+pub const MAX_PARTICLES: usize = 2000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleShape {
+    Dot,  // `size` is the radius
+    Line, // `size` is the length, turning with `rotation`
+}
+
+/// What a particle effect looks like and how it moves.
+#[derive(Clone, Copy, Debug)]
+pub struct Preset {
+    pub count: u32,                  // at a scale of 1
+    pub speed: (f32, f32),           // px/s, on top of the emitter's own velocity
+    pub spread: f32,                 // width of the cone they leave in, in radians; 2π is all round
+    pub lifetime: (f32, f32),        // seconds
+    pub size: (f32, f32),            // at a scale of 1
+    pub shrink: f32,                 // fraction of its size a particle keeps per second
+    pub drag: f32,                   // fraction of its velocity a particle keeps per second
+    pub spin: f32,                   // up to this many radians per second either way
+    pub colors: (Color, Color),      // at birth and at death, blended in between
+    pub shape: ParticleShape,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Emitter {
+    ShipExplosion,
+    AsteroidDebris,
+    ShipDebris,
+    ThrustExhaust,
+    ImpactSparks,
+}
+
+impl Emitter {
+    pub fn preset(&self) -> Preset {
+        match self {
+            // Hot white flash cooling to red
+            Emitter::ShipExplosion => Preset {
+                count: 60,
+                speed: (60.0, 320.0),
+                spread: 2.0 * PI,
+                lifetime: (0.6, 1.6),
+                size: (2.0, 5.0),
+                shrink: 0.3,
+                drag: 0.4,
+                spin: 0.0,
+                colors: (Color::new(1.0, 1.0, 0.9, 1.0), Color::new(0.8, 0.1, 0.0, 0.0)),
+                shape: ParticleShape::Dot,
+            },
+            // Tumbling bits of rock outline
+            Emitter::AsteroidDebris => Preset {
+                count: 10,
+                speed: (40.0, 160.0),
+                spread: 2.0 * PI,
+                lifetime: (0.5, 1.2),
+                size: (4.0, 12.0),
+                shrink: 0.5,
+                drag: 0.5,
+                spin: 8.0,
+                colors: (Color::WHITE, Color::new(0.5, 0.5, 0.5, 0.0)),
+                shape: ParticleShape::Line,
+            },
+            // The hull's own sides drifting apart; see `ParticleSystem::shatter`
+            Emitter::ShipDebris => Preset {
+                count: 0, // one per edge
+                speed: (20.0, 60.0),
+                spread: 0.0,
+                lifetime: (1.5, 2.5),
+                size: (1.0, 1.0), // the edge's length
+                shrink: 1.0,
+                drag: 0.6,
+                spin: 3.0,
+                colors: (Color::WHITE, Color::new(1.0, 1.0, 1.0, 0.0)),
+                shape: ParticleShape::Line,
+            },
+            // Short-lived, out the back, every tick the engine runs
+            Emitter::ThrustExhaust => Preset {
+                count: 1,
+                speed: (120.0, 220.0),
+                spread: 0.5,
+                lifetime: (0.15, 0.35),
+                size: (1.5, 3.0),
+                shrink: 0.1,
+                drag: 0.2,
+                spin: 0.0,
+                colors: (Color::new(1.0, 0.9, 0.5, 1.0), Color::new(1.0, 0.3, 0.0, 0.0)),
+                shape: ParticleShape::Dot,
+            },
+            // Where a bullet strikes, glancing back the way it came
+            Emitter::ImpactSparks => Preset {
+                count: 8,
+                speed: (150.0, 350.0),
+                spread: 1.6,
+                lifetime: (0.1, 0.3),
+                size: (1.0, 2.0),
+                shrink: 0.2,
+                drag: 0.05,
+                spin: 0.0,
+                colors: (Color::WHITE, Color::new(1.0, 0.8, 0.2, 0.0)),
+                shape: ParticleShape::Dot,
+            },
+        }
+    }
+}
+
+pub struct Particle {
+    pub position: Vec2,
+    pub prev_position: Vec2,
+    pub velocity: Vec2,
+    pub rotation: f32,
+    pub prev_rotation: f32,
+    pub spin: f32,
+    pub age: f32,
+    pub lifetime: f32,
+    pub size: f32,
+    pub shrink: f32,
+    pub drag: f32,
+    pub colors: (Color, Color),
+    pub shape: ParticleShape,
+}
+
+impl Particle {
+    pub fn update(&mut self, dt: f32) {
+        self.prev_position = self.position;
+        self.prev_rotation = self.rotation;
+        self.velocity *= self.drag.powf(dt);
+        self.position += self.velocity * dt;
+        self.rotation += self.spin * dt;
+        self.size *= self.shrink.powf(dt);
+        self.age += dt;
+    }
+
+    pub fn alive(&self) -> bool {
+        self.age < self.lifetime
+    }
+
+    /// Along the preset's gradient by how much of its life is used up.
+    pub fn color(&self) -> Color {
+        let t = (self.age / self.lifetime).clamp(0.0, 1.0);
+        let (from, to) = self.colors;
+        Color::new(
+            from.r + (to.r - from.r) * t,
+            from.g + (to.g - from.g) * t,
+            from.b + (to.b - from.b) * t,
+            from.a + (to.a - from.a) * t,
+        )
+    }
+
+    /// A unit circle, scaled to each dot's size in its `Batch`.
+    pub fn dot_mesh(ctx: &Context) -> GameResult<Mesh> {
+        Mesh::new_circle(ctx, DrawMode::fill(), Vec2::ZERO, 1.0, 0.01, Color::WHITE)
+    }
+
+    /// A unit-length segment, stretched to each line's length in its `Batch`.
+    pub fn line_mesh(ctx: &Context) -> GameResult<Mesh> {
+        Mesh::new_line(ctx, &[Vec2::new(-0.5, 0.0), Vec2::new(0.5, 0.0)], 2.0, Color::WHITE)
+    }
+
+    pub fn draw(&self, dots: &mut Batch, lines: &mut Batch, alpha: f32) {
+        let param = DrawParam::default()
+            .dest(self.prev_position.lerp(self.position, alpha))
+            .color(self.color());
+        match self.shape {
+            ParticleShape::Dot => dots.push(param.scale(Vec2::splat(self.size))),
+            ParticleShape::Line => {
+                let rotation = self.prev_rotation + (self.rotation - self.prev_rotation) * alpha;
+                lines.push(param.rotation(rotation).scale(Vec2::new(self.size, 1.0)));
+            }
+        }
+    }
+}
+
+/// All live particles, oldest first.
+pub struct ParticleSystem {
+    particles: VecDeque<Particle>,
+    rng: StdRng, // effects only; the game's own stream is left alone
+}
+
+impl ParticleSystem {
+    pub fn new(seed: u64) -> ParticleSystem {
+        ParticleSystem {
+            particles: VecDeque::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Let off one burst of `emitter` at `position`. `velocity` is carried
+    /// by every particle, `heading` is where the cone points and `scale`
+    /// makes for more and bigger particles.
+    pub fn emit(&mut self, emitter: Emitter, position: Vec2, velocity: Vec2, heading: f32, scale: f32) {
+        let preset = emitter.preset();
+        let count = (preset.count as f32 * scale).round().max(1.0) as usize;
+        for _ in 0..count {
+            let rng = &mut self.rng;
+            let angle = heading + rng.gen_range(-0.5..=0.5) * preset.spread;
+            let rotation = rng.gen_range(0.0..2.0 * PI);
+            let particle = Particle {
+                position,
+                prev_position: position,
+                velocity: velocity + Vec2::from_angle(angle) * rng.gen_range(preset.speed.0..=preset.speed.1),
+                rotation,
+                prev_rotation: rotation,
+                spin: rng.gen_range(-preset.spin..=preset.spin),
+                age: 0.0,
+                lifetime: rng.gen_range(preset.lifetime.0..=preset.lifetime.1),
+                size: rng.gen_range(preset.size.0..=preset.size.1) * scale,
+                shrink: preset.shrink,
+                drag: preset.drag,
+                colors: preset.colors,
+                shape: preset.shape,
+            };
+            self.push(particle);
+        }
+    }
+
+    /// Break an outline into its edges, each one a line that tumbles off
+    /// away from the middle. Only speed, spin, lifetime, drag and colours
+    /// are taken from the preset; the lines keep the edges' lengths.
+    pub fn shatter(&mut self, emitter: Emitter, outline: &[Vec2], velocity: Vec2) {
+        let preset = emitter.preset();
+        let centre = outline.iter().copied().sum::<Vec2>() / outline.len() as f32;
+        for (i, &a) in outline.iter().enumerate() {
+            let b = outline[(i + 1) % outline.len()];
+            let rng = &mut self.rng;
+            let middle = (a + b) / 2.0;
+            let edge = b - a;
+            let rotation = edge.y.atan2(edge.x);
+            let outward = (middle - centre).try_normalize().unwrap_or(Vec2::X);
+            let particle = Particle {
+                position: middle,
+                prev_position: middle,
+                velocity: velocity + outward * rng.gen_range(preset.speed.0..=preset.speed.1),
+                rotation,
+                prev_rotation: rotation,
+                spin: rng.gen_range(-preset.spin..=preset.spin),
+                age: 0.0,
+                lifetime: rng.gen_range(preset.lifetime.0..=preset.lifetime.1),
+                size: edge.length(),
+                shrink: preset.shrink,
+                drag: preset.drag,
+                colors: preset.colors,
+                shape: preset.shape,
+            };
+            self.push(particle);
+        }
+    }
+
+    /// Add a particle, making room by dropping the oldest if need be.
+    pub fn push(&mut self, particle: Particle) {
+        if self.particles.len() >= MAX_PARTICLES {
+            self.particles.pop_front();
+        }
+        self.particles.push_back(particle);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.particles.retain_mut(|particle| {
+            particle.update(dt);
+            particle.alive()
+        });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.particles.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_cap_drops_the_oldest() {
+        let mut particles = ParticleSystem::new(1);
+        let (first, last) = (Vec2::new(-100.0, 0.0), Vec2::new(100.0, 0.0));
+        particles.emit(Emitter::ShipExplosion, first, Vec2::ZERO, 0.0, 1.0);
+        for _ in 0..MAX_PARTICLES {
+            particles.emit(Emitter::ImpactSparks, Vec2::ZERO, Vec2::ZERO, 0.0, 1.0);
+        }
+        particles.emit(Emitter::ShipExplosion, last, Vec2::ZERO, 0.0, 1.0);
+        assert_eq!(particles.len(), MAX_PARTICLES);
+        assert!(particles.iter().all(|particle| particle.position != first));
+        assert_eq!(particles.iter().last().map(|particle| particle.position), Some(last));
+    }
+}
@@ -0,0 +1,39 @@
+// Stapelweise zeichnen (Batched drawing)
+// πολλὰ ἐν ἑνί: one mesh, drawn wherever it's needed in a single call.
+//
+// Bullets and particles come in hundreds and all look alike, so instead of
+// building a mesh for each of them every frame they push a transform here
+// and the whole lot goes to the GPU at once.
+
+use ggez::{
+    graphics::{Canvas, DrawParam, InstanceArray, Mesh},
+    Context,
+};
+
+pub struct Batch {
+    mesh: Mesh,
+    instances: InstanceArray,
+}
+
+impl Batch {
+    pub fn new(ctx: &Context, mesh: Mesh) -> Batch {
+        Batch {
+            mesh,
+            instances: InstanceArray::new(ctx, None),
+        }
+    }
+
+    /// Queue one copy of the mesh, placed, scaled and tinted by `param`.
+    pub fn push(&mut self, param: DrawParam) {
+        self.instances.push(param);
+    }
+
+    /// Draw everything queued since last time in one go, and start over.
+    pub fn flush(&mut self, canvas: &mut Canvas) {
+        if self.instances.instances().is_empty() {
+            return;
+        }
+        canvas.draw_instanced_mesh(self.mesh.clone(), &self.instances, DrawParam::default());
+        self.instances.clear();
+    }
+}
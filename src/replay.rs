@@ -0,0 +1,203 @@
+/*
+ * Replay files: the seed, the arena size, the rules from the config and
+ * every input with the tick it arrived on. The simulation is deterministic,
+ * so that's all it takes to play a session back exactly.
+ *
+ *   # asteroids replay v1
+ *   seed 1234
+ *   arena 1600 1200
+ *   config asteroid_collisions on
+ *   118 press Thrust
+ *   164 release Thrust
+ *   240 turn -0.65
+ *   900 arena 1280 960
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::input::{Action, InputEvent};
+
+const HEADER: &str = "# asteroids replay v1";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplayEvent {
+    Input(InputEvent),
+    Arena(f32, f32), // the window was resized
+}
+
+pub struct Replay {
+    pub seed: u64,
+    pub width: f32,
+    pub height: f32,
+    pub config: Config, // older replays without config lines ran on the defaults
+    pub events: Vec<(u64, ReplayEvent)>, // tick, event
+}
+
+impl Replay {
+    pub fn new(seed: u64, width: f32, height: f32, config: Config) -> Replay {
+        Replay {
+            seed,
+            width,
+            height,
+            config,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, tick: u64, event: ReplayEvent) {
+        self.events.push((tick, event));
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load(path: &Path) -> io::Result<Replay> {
+        Replay::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{}\nseed {}\narena {} {}\n",
+            HEADER, self.seed, self.width, self.height
+        );
+        for (name, value) in self.config.rules() {
+            text.push_str(&format!("config {} {}\n", name, value));
+        }
+        for (tick, event) in &self.events {
+            let line = match event {
+                ReplayEvent::Input(InputEvent::Pressed(action)) => format!("{} press {}", tick, action.name()),
+                ReplayEvent::Input(InputEvent::Released(action)) => format!("{} release {}", tick, action.name()),
+                ReplayEvent::Input(InputEvent::Turn(amount)) => format!("{} turn {}", tick, amount),
+                ReplayEvent::Arena(width, height) => format!("{} arena {} {}", tick, width, height),
+            };
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> io::Result<Replay> {
+        let bad = |line: &str| io::Error::new(io::ErrorKind::InvalidData, format!("bad replay line: {}", line));
+
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        if lines.next() != Some(HEADER) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not an asteroids replay"));
+        }
+
+        // Without a seed and an arena there's no game to play back
+        let mut seed = None;
+        let mut arena = None;
+        let mut config = Config::default();
+        let mut events = Vec::new();
+        for line in lines {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["seed", value] => seed = Some(value.parse().map_err(|_| bad(line))?),
+                ["config", name, value] => config.set(name, value).ok_or_else(|| bad(line))?,
+                ["arena", width, height] => arena = Some(parse_arena(width, height).ok_or_else(|| bad(line))?),
+                [tick, kind, rest @ ..] => {
+                    let tick = tick.parse().map_err(|_| bad(line))?;
+                    let event = match (*kind, rest) {
+                        ("press", [name]) => ReplayEvent::Input(InputEvent::Pressed(
+                            Action::from_name(name).ok_or_else(|| bad(line))?,
+                        )),
+                        ("release", [name]) => ReplayEvent::Input(InputEvent::Released(
+                            Action::from_name(name).ok_or_else(|| bad(line))?,
+                        )),
+                        ("turn", [amount]) => ReplayEvent::Input(InputEvent::Turn(
+                            amount.parse().ok().filter(|amount: &f32| (-1.0..=1.0).contains(amount)).ok_or_else(|| bad(line))?,
+                        )),
+                        ("arena", [width, height]) => {
+                            let (width, height) = parse_arena(width, height).ok_or_else(|| bad(line))?;
+                            ReplayEvent::Arena(width, height)
+                        }
+                        _ => return Err(bad(line)),
+                    };
+                    events.push((tick, event));
+                }
+                _ => return Err(bad(line)),
+            }
+        }
+
+        let missing = |what: &str| io::Error::new(io::ErrorKind::InvalidData, format!("replay has no {} line", what));
+        let seed = seed.ok_or_else(|| missing("seed"))?;
+        let (width, height) = arena.ok_or_else(|| missing("arena"))?;
+        let mut replay = Replay::new(seed, width, height, config);
+        replay.events = events;
+        Ok(replay)
+    }
+}
+
+/// A width and height, both finite and above zero; a world can't be any smaller.
+fn parse_arena(width: &str, height: &str) -> Option<(f32, f32)> {
+    let positive = |value: &str| value.parse::<f32>().ok().filter(|size| size.is_finite() && *size > 0.0);
+    Some((positive(width)?, positive(height)?))
+}
+
+/// Hands recorded events back out in order as the simulation reaches their tick.
+pub struct ReplayPlayer {
+    replay: Replay,
+    cursor: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> ReplayPlayer {
+        ReplayPlayer { replay, cursor: 0 }
+    }
+
+    /// The next event recorded at or before `tick`, if any is still pending.
+    pub fn next_due(&mut self, tick: u64) -> Option<ReplayEvent> {
+        let (event_tick, event) = *self.replay.events.get(self.cursor)?;
+        if event_tick > tick {
+            return None;
+        }
+        self.cursor += 1;
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let config = Config { asteroid_collisions: true, max_lives: 7, ..Config::default() };
+        let mut replay = Replay::new(1234, 1600.0, 1200.0, config);
+        replay.record(118, ReplayEvent::Input(InputEvent::Pressed(Action::Thrust)));
+        replay.record(164, ReplayEvent::Input(InputEvent::Released(Action::Thrust)));
+        replay.record(240, ReplayEvent::Input(InputEvent::Turn(-0.65)));
+        replay.record(250, ReplayEvent::Input(InputEvent::Turn(1.0 / 3.0)));
+        replay.record(900, ReplayEvent::Arena(1280.0, 960.0));
+
+        let parsed = Replay::parse(&replay.to_text()).unwrap();
+        assert_eq!(parsed.seed, replay.seed);
+        assert_eq!((parsed.width, parsed.height), (replay.width, replay.height));
+        assert_eq!(parsed.config, replay.config);
+        assert_eq!(parsed.events, replay.events);
+    }
+
+    #[test]
+    fn rejects_other_files_and_bad_lines() {
+        assert!(Replay::parse("seed 1\narena 800 600\n").is_err());
+        assert!(Replay::parse("# asteroids replay v1\nseed 1\narena 800 600\n10 press Jump\n").is_err());
+        assert!(Replay::parse("# asteroids replay v1\nseed 1\narena 800 600\nconfig asteroid_collisions maybe\n").is_err());
+    }
+
+    #[test]
+    fn needs_a_seed_and_an_arena_to_play_in() {
+        let invalid = |text: &str| Replay::parse(text).err().map(|e| e.kind()) == Some(io::ErrorKind::InvalidData);
+        assert!(invalid("# asteroids replay v1\nseed 5\n10 press Fire\n"));
+        assert!(invalid("# asteroids replay v1\narena 800 600\n"));
+        assert!(invalid("# asteroids replay v1\nseed 5\narena 0 600\n"));
+        assert!(invalid("# asteroids replay v1\nseed 5\narena 800 -1\n"));
+        assert!(invalid("# asteroids replay v1\nseed 5\narena 800 600\n10 arena 0 0\n"));
+        assert!(invalid("# asteroids replay v1\nseed 5\narena 800 600\n10 arena NaN 600\n"));
+        assert!(invalid("# asteroids replay v1\nseed 5\narena 800 600\n10 turn 1.5\n"));
+        assert!(invalid("# asteroids replay v1\nseed 5\narena 800 600\n10 turn NaN\n"));
+    }
+}
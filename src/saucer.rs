@@ -0,0 +1,223 @@
+use ggez::{
+    graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder},
+    Context, GameResult,
+};
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::cell::OnceCell;
+use std::f32::consts::PI;
+
+use crate::bullet::Bullet;
+use crate::world::ghost_offsets;
+
+// Outlines in units of the saucer's radius
+const HULL: [Vec2; 6] = [
+    Vec2::new(-1.0, 0.0),
+    Vec2::new(-0.5, 0.4),
+    Vec2::new(0.5, 0.4),
+    Vec2::new(1.0, 0.0),
+    Vec2::new(0.5, -0.3),
+    Vec2::new(-0.5, -0.3),
+];
+const DOME: [Vec2; 4] = [
+    Vec2::new(-0.35, -0.3),
+    Vec2::new(-0.2, -0.65),
+    Vec2::new(0.2, -0.65),
+    Vec2::new(0.35, -0.3),
+];
+
+const BULLET_SPEED: f32 = 400.0;
+const BULLET_LIFETIME: f32 = 1.2;
+
+// Fliegende Untertasse (Flying saucer)
+// ξένος
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaucerSize {
+    Large, // fires at random
+    Small, // aims at the ship, better the higher the score
+}
+
+impl SaucerSize {
+    pub fn radius(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 30.0,
+            SaucerSize::Small => 15.0,
+        }
+    }
+
+    pub fn points(&self) -> i32 {
+        match self {
+            SaucerSize::Large => 200,
+            SaucerSize::Small => 1000,
+        }
+    }
+
+    /// In `Asteroid::mass` units, for ramming asteroids.
+    pub fn mass(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 2.0,
+            SaucerSize::Small => 0.5,
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 120.0,
+            SaucerSize::Small => 180.0,
+        }
+    }
+
+    fn fire_interval(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 1.2,
+            SaucerSize::Small => 1.0,
+        }
+    }
+}
+
+pub struct Saucer {
+    pub position: Vec2,
+    pub prev_position: Vec2,
+    pub velocity: Vec2,
+    pub size: SaucerSize,
+    heading_timer: f32, // until the next change of course
+    fire_timer: f32,
+    mesh: OnceCell<Mesh>, // hull, dome and rim in one, built on the first draw and kept
+}
+
+impl Saucer {
+    /// Enter from the left or right edge at a random height.
+    pub fn new(rng: &mut StdRng, width: f32, height: f32, size: SaucerSize) -> Self {
+        let from_left = rng.gen_bool(0.5);
+        let radius = size.radius();
+        let position = Vec2::new(
+            if from_left { -radius } else { width + radius },
+            rng.gen_range(height * 0.1..height * 0.9),
+        );
+        let direction = if from_left { 1.0 } else { -1.0 };
+        Saucer {
+            position,
+            prev_position: position,
+            velocity: Vec2::new(direction * size.speed(), 0.0),
+            size,
+            heading_timer: rng.gen_range(1.0..2.0),
+            fire_timer: size.fire_interval(),
+            mesh: OnceCell::new(),
+        }
+    }
+
+    /// Returns false once the saucer has crossed the screen and flown off.
+    pub fn update(&mut self, rng: &mut StdRng, dt: f32, width: f32, height: f32) -> bool {
+        self.prev_position = self.position;
+        self.position += self.velocity * dt;
+
+        // Zickzack: every so often, veer diagonally or straighten out
+        self.heading_timer -= dt;
+        if self.heading_timer <= 0.0 {
+            self.heading_timer = rng.gen_range(1.0..2.0);
+            let speed = self.size.speed();
+            self.velocity.y = [-0.5, 0.0, 0.5][rng.gen_range(0..3)] * speed;
+        }
+
+        // Off the far side means gone; top and bottom wrap as usual
+        let radius = self.size.radius();
+        if self.position.x < -radius * 2.0 || self.position.x > width + radius * 2.0 {
+            return false;
+        }
+        let before = self.position.y;
+        if self.position.y < 0.0 {
+            self.position.y = height;
+        } else if self.position.y > height {
+            self.position.y = 0.0;
+        }
+        self.prev_position.y += self.position.y - before;
+        true
+    }
+
+    /// The hull in world space; the dome on top is only decoration.
+    pub fn outline(&self) -> Vec<Vec2> {
+        let r = self.size.radius();
+        HULL.iter().map(|point| *point * r + self.position).collect()
+    }
+
+    /// Count down to the next shot and take it if due. The large saucer
+    /// sprays at random; the small one aims at `target`, missing by less
+    /// the higher `score` gets.
+    pub fn fire(&mut self, rng: &mut StdRng, dt: f32, target: Option<Vec2>, score: i32) -> Option<Bullet> {
+        self.fire_timer -= dt;
+        if self.fire_timer > 0.0 {
+            return None;
+        }
+        self.fire_timer = self.size.fire_interval();
+
+        let angle = match (self.size, target) {
+            (SaucerSize::Small, Some(target)) => {
+                let to_target = target - self.position;
+                let max_error = (0.5 - score as f32 / 40_000.0).max(0.05);
+                to_target.y.atan2(to_target.x) + rng.gen_range(-max_error..max_error)
+            }
+            _ => rng.gen_range(0.0..2.0 * PI),
+        };
+        let direction = Vec2::new(angle.cos(), angle.sin());
+        let position = self.position + direction * self.size.radius();
+        Some(Bullet {
+            position,
+            prev_position: position,
+            velocity: direction * BULLET_SPEED,
+            lifetime: BULLET_LIFETIME,
+        })
+    }
+
+    /// Ghosts only above and below; off the sides the saucer is just gone.
+    pub fn draw(&self, canvas: &mut Canvas, ctx: &Context, alpha: f32, height: f32) -> GameResult {
+        let r = self.size.radius();
+        if self.mesh.get().is_none() {
+            let scaled = |points: &[Vec2]| -> Vec<Vec2> { points.iter().map(|point| *point * r).collect() };
+            let mut builder = MeshBuilder::new();
+            builder
+                .polygon(DrawMode::stroke(2.0), &scaled(&HULL), Color::WHITE)?
+                .line(&scaled(&DOME), 2.0, Color::WHITE)?
+                .line(&scaled(&[HULL[0], HULL[3]]), 2.0, Color::WHITE)?;
+            let _ = self.mesh.set(Mesh::from_data(ctx, builder.build()));
+        }
+        let Some(mesh) = self.mesh.get() else {
+            return Ok(());
+        };
+
+        let position = self.prev_position.lerp(self.position, alpha);
+        for offset in ghost_offsets(position, r, 0.0, height) {
+            canvas.draw(mesh, DrawParam::default().dest(position + offset));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn fires_once_per_interval() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut saucer = Saucer::new(&mut rng, 1280.0, 960.0, SaucerSize::Large);
+        let dt = 1.0 / 120.0;
+        let shots = (0..780).filter(|_| saucer.fire(&mut rng, dt, None, 0).is_some()).count();
+        assert_eq!(shots, 5); // 6.5 s at one every 1.2
+    }
+
+    #[test]
+    fn the_small_saucer_aims_closer_the_higher_the_score() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for (score, max_error) in [(0, 0.5), (10_000, 0.25), (40_000, 0.05)] {
+            for _ in 0..50 {
+                let mut saucer = Saucer::new(&mut rng, 1280.0, 960.0, SaucerSize::Small);
+                let target = saucer.position + Vec2::new(rng.gen_range(-500.0..500.0), rng.gen_range(-500.0..500.0));
+                let bullet = saucer.fire(&mut rng, SaucerSize::Small.fire_interval(), Some(target), score).unwrap();
+                let error = bullet.velocity.angle_between(target - saucer.position).abs();
+                assert!(error <= max_error + 1e-4, "score {}: off by {}", score, error);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// Formgebung (Shape generation) for asteroids
+// σχῆμα: lumpy, but never a tangle and never a needle.
+//
+// Vertices go round the centre at steadily increasing angles, each at its
+// own random distance. A polygon like that (star-shaped around its centre)
+// can't cross itself. Corners sharper than the minimum angle get their
+// vertex pulled in towards its neighbours until they aren't.
+
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::f32::consts::PI;
+
+use crate::collision;
+
+const SMOOTHING_PASSES: usize = 16;
+const MIN_VERTICES: usize = 5; // any fewer and there's no room left for the minimum angle
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapeParams {
+    pub min_vertices: usize,
+    pub max_vertices: usize,
+    pub roughness: f32, // how far each vertex may stray from the radius, as a fraction of it
+    pub min_angle: f32, // sharpest corner allowed, in radians
+}
+
+impl Default for ShapeParams {
+    fn default() -> Self {
+        ShapeParams {
+            min_vertices: 8,
+            max_vertices: 12,
+            roughness: 0.4,
+            min_angle: 70.0_f32.to_radians(),
+        }
+    }
+}
+
+/// An outline in local space, moved so that its centroid sits on the
+/// origin: that is what it turns around and where its mass is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    pub points: Vec<Vec2>,
+    pub area: f32,
+    pub bounding_radius: f32,
+}
+
+impl Shape {
+    pub fn generate(rng: &mut StdRng, radius: f32, params: &ShapeParams) -> Shape {
+        let min_vertices = params.min_vertices.max(MIN_VERTICES);
+        let count = rng.gen_range(min_vertices..=params.max_vertices.max(min_vertices));
+        let step = 2.0 * PI / count as f32;
+
+        // Jittered by less than half a step, so the angles still only go up
+        let angles: Vec<f32> = (0..count)
+            .map(|i| (i as f32 + rng.gen_range(-0.3..0.3)) * step)
+            .collect();
+        let roughness = params.roughness.clamp(0.0, 0.9);
+        let mut radii: Vec<f32> = (0..count)
+            .map(|_| radius * (1.0 + rng.gen_range(-roughness..=roughness)))
+            .collect();
+
+        // With all radii equal every corner is at least π - 1.6 steps, so
+        // asking for more than that could never be met
+        let min_angle = params.min_angle.min(PI - 1.6 * step);
+        for _ in 0..SMOOTHING_PASSES {
+            let points = polar(&angles, &radii);
+            let sharp: Vec<usize> = (0..count)
+                .filter(|&i| interior_angle(&points, i) < min_angle)
+                .collect();
+            if sharp.is_empty() {
+                break;
+            }
+            for i in sharp {
+                let neighbours = (radii[(i + count - 1) % count] + radii[(i + 1) % count]) / 2.0;
+                radii[i] = (radii[i] + neighbours) / 2.0;
+            }
+        }
+        let mut points = polar(&angles, &radii);
+        if (0..count).any(|i| interior_angle(&points, i) < min_angle) {
+            points = polar(&angles, &vec![radius; count]);
+        }
+        debug_assert!(collision::is_simple(&points));
+
+        let centre = centroid(&points);
+        for point in &mut points {
+            *point -= centre;
+        }
+        Shape {
+            area: area(&points),
+            bounding_radius: collision::bounding_radius(&points),
+            points,
+        }
+    }
+}
+
+fn polar(angles: &[f32], radii: &[f32]) -> Vec<Vec2> {
+    angles.iter().zip(radii).map(|(angle, radius)| Vec2::from_angle(*angle) * *radius).collect()
+}
+
+/// Inside angle at vertex `i` of a counter-clockwise polygon; over π where
+/// the outline dents inwards.
+pub fn interior_angle(points: &[Vec2], i: usize) -> f32 {
+    let count = points.len();
+    let (previous, point, next) = (points[(i + count - 1) % count], points[i], points[(i + 1) % count]);
+    let (incoming, outgoing) = (point - previous, next - point);
+    let turn = incoming.perp_dot(outgoing).atan2(incoming.dot(outgoing));
+    PI - turn
+}
+
+/// Shoelace formula; positive for counter-clockwise outlines.
+pub fn area(points: &[Vec2]) -> f32 {
+    edges(points).map(|(a, b)| a.perp_dot(b)).sum::<f32>() / 2.0
+}
+
+/// Centre of mass of the enclosed area, assuming it's even.
+pub fn centroid(points: &[Vec2]) -> Vec2 {
+    let area = area(points);
+    if area.abs() < f32::EPSILON {
+        return points.iter().copied().sum::<Vec2>() / points.len().max(1) as f32;
+    }
+    edges(points).map(|(a, b)| (a + b) * a.perp_dot(b)).sum::<Vec2>() / (6.0 * area)
+}
+
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    points.iter().zip(points.iter().cycle().skip(1)).map(|(a, b)| (*a, *b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn outlines_are_simple_and_never_too_sharp() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let params = ShapeParams::default();
+        for _ in 0..500 {
+            let shape = Shape::generate(&mut rng, 40.0, &params);
+            let points = &shape.points;
+            assert!(points.len() >= params.min_vertices && points.len() <= params.max_vertices);
+            assert!(collision::is_simple(points));
+            assert!(shape.area > 0.0);
+            for i in 0..points.len() {
+                assert!(interior_angle(points, i) >= params.min_angle - 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn centroid_sits_on_the_origin() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let params = ShapeParams { min_vertices: 3, max_vertices: 5, roughness: 0.9, min_angle: PI };
+        for _ in 0..100 {
+            let shape = Shape::generate(&mut rng, 20.0, &params);
+            assert!(shape.points.len() >= MIN_VERTICES);
+            assert!(centroid(&shape.points).length() < 1e-3);
+        }
+    }
+}
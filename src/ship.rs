@@ -0,0 +1,193 @@
+use ggez::{
+    graphics::{Canvas, Color, DrawMode, DrawParam, Mesh},
+    Context, GameResult,
+};
+use glam::Vec2;
+
+use crate::bullet::Bullet;
+use crate::collision;
+use crate::world::{ghost_offsets, interpolate_angle, wrap_position};
+
+/// Fraction of its velocity the ship keeps after one second of drifting
+/// (0.99 per frame at 60 FPS).
+const DRAG_PER_SECOND: f32 = 0.547;
+
+/// Hull triangle around the ship's position, nose along +x.
+const SHAPE: [Vec2; 3] = [
+    Vec2::new(40.0, 0.0),
+    Vec2::new(-20.0, 20.0),
+    Vec2::new(-20.0, -20.0),
+];
+
+/// Where the exhaust leaves, in the middle of the hull's back edge.
+const NOZZLE: Vec2 = Vec2::new(-20.0, 0.0);
+
+/// How the ship turns while a rotate key is held.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TurnRule {
+    pub speed: f32,        // radians per second at full turn
+    pub acceleration: f32, // radians per second², to get there and to stop; 0 for at once
+}
+
+/// How fast the ship can shoot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FireRule {
+    pub cooldown: f32,      // seconds from one shot to the next
+    pub max_bullets: usize, // of the player's own in flight at once
+    pub auto: bool,         // holding fire keeps shooting
+}
+
+/// How long the ship is gone between jumping and re-entering.
+const HYPERSPACE_TIME: f32 = 0.5;
+
+// κίνησις πάντων
+pub struct Ship {
+    pub position: Vec2,
+    pub prev_position: Vec2,
+    pub velocity: Vec2,
+    pub rotation: f32,
+    pub prev_rotation: f32,
+    pub angular_velocity: f32,
+    pub fire_timer: f32,  // > 0 while the gun is cooling down
+    pub invulnerable: bool,
+    pub invulnerable_timer: f32,
+    pub hyperspace_timer: f32, // > 0 while the ship is in hyperspace
+}
+
+/// The hull, built once and placed with a transform every frame.
+pub struct ShipMeshes {
+    pub hull: Mesh,
+}
+
+impl ShipMeshes {
+    pub fn new(ctx: &Context) -> GameResult<ShipMeshes> {
+        Ok(ShipMeshes {
+            hull: Mesh::new_polygon(ctx, DrawMode::stroke(4.0), &SHAPE, Color::WHITE)?,
+        })
+    }
+}
+
+// Neue Instanz des Raumschiffs erstellen (Create new ship instance)
+impl Ship {
+    pub fn new() -> Self {
+        Ship {
+            position: Vec2::ZERO,  // Wird in reset_position gesetzt
+            prev_position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            prev_rotation: 0.0,
+            angular_velocity: 0.0,
+            fire_timer: 0.0,
+            invulnerable: true,
+            invulnerable_timer: 3.0,
+            hyperspace_timer: 0.0,
+        }
+    }
+
+    pub fn reset_position(&mut self, width: f32, height: f32) {
+        self.position = Vec2::new(width / 2.0, height / 2.0);
+        self.prev_position = self.position;
+    }
+
+    pub fn in_hyperspace(&self) -> bool {
+        self.hyperspace_timer > 0.0
+    }
+
+    /// Can be hit: not freshly spawned and not off in hyperspace.
+    pub fn vulnerable(&self) -> bool {
+        !self.invulnerable && !self.in_hyperspace()
+    }
+
+    // Hyperraum (Hyperspace): vanish now, reappear at `destination` later
+    pub fn jump_to(&mut self, destination: Vec2) {
+        self.position = destination;
+        self.prev_position = destination;
+        self.velocity = Vec2::ZERO;
+        self.angular_velocity = 0.0;
+        self.hyperspace_timer = HYPERSPACE_TIME;
+    }
+
+    /// The hull triangle in world space, exactly as drawn.
+    pub fn outline(&self) -> Vec<Vec2> {
+        collision::transform(&SHAPE, self.position, self.rotation)
+    }
+
+    /// The back of the hull in world space, where thrust comes out.
+    pub fn nozzle(&self) -> Vec2 {
+        collision::transform(&[NOZZLE], self.position, self.rotation)[0]
+    }
+
+    pub fn bounding_radius(&self) -> f32 {
+        collision::bounding_radius(&SHAPE)
+    }
+
+    /// `turning` is how hard the player turns, from -1 (left) to 1 (right);
+    /// `thrust` whether the engine is on.
+    pub fn update(&mut self, dt: f32, width: f32, height: f32, turn: TurnRule, turning: f32, thrust: bool) {
+        self.prev_position = self.position;
+        self.prev_rotation = self.rotation;
+
+        if self.in_hyperspace() {
+            self.hyperspace_timer -= dt;
+            return;
+        }
+
+        self.fire_timer -= dt;
+        if self.invulnerable {
+            self.invulnerable_timer -= dt;
+            if self.invulnerable_timer <= 0.0 {
+                self.invulnerable = false;
+            }
+        }
+
+        // Drehung (Turning)
+        let target = turning.clamp(-1.0, 1.0) * turn.speed;
+        if turn.acceleration > 0.0 {
+            let change = turn.acceleration * dt;
+            self.angular_velocity += (target - self.angular_velocity).clamp(-change, change);
+        } else {
+            self.angular_velocity = target;
+        }
+        self.rotation += self.angular_velocity * dt;
+
+        if thrust {
+            let thrust_direction = Vec2::new(self.rotation.cos(), self.rotation.sin());
+            self.velocity += thrust_direction * 200.0 * dt;
+        }
+
+        self.velocity *= DRAG_PER_SECOND.powf(dt);
+        self.position += self.velocity * dt;
+
+        wrap_position(&mut self.position, &mut self.prev_position, width, height);
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, ctx: &Context, meshes: &ShipMeshes, alpha: f32, width: f32, height: f32) {
+        if self.in_hyperspace() || (self.invulnerable && ctx.time.ticks().is_multiple_of(2)) {
+            return;
+        }
+
+        let position = self.prev_position.lerp(self.position, alpha);
+        let rotation = interpolate_angle(self.prev_rotation, self.rotation, alpha);
+        for offset in ghost_offsets(position, self.bounding_radius(), width, height) {
+            let param = DrawParam::default().dest(position + offset).rotation(rotation);
+            canvas.draw(&meshes.hull, param);
+        }
+    }
+
+    pub fn shoot(&self) -> Bullet {
+        let direction = Vec2::new(self.rotation.cos(), self.rotation.sin());
+        let position = self.position + direction * 40.0;
+        Bullet {
+            position,
+            prev_position: position,
+            velocity: direction * 800.0 + self.velocity,
+            lifetime: 1.0,
+        }
+    }
+}
+
+impl Default for Ship {
+    fn default() -> Self {
+        Self::new()
+    }
+}
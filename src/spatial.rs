@@ -0,0 +1,135 @@
+// Räumliches Raster (Spatial grid) for the collision broad phase
+// τόπος: only test what shares a neighbourhood.
+//
+// The arena is cut into equal cells, as close to the asked-for size as
+// divides it evenly, so cell indices wrap around exactly like the screen
+// does. Everything is filed under the one cell its centre is in, and
+// queries widen by the largest radius filed so far to make up for it.
+
+use glam::Vec2;
+
+pub struct SpatialHash {
+    columns: usize,
+    rows: usize,
+    cell: Vec2, // width and height of one cell
+    cells: Vec<Vec<usize>>,
+    reach: f32, // largest radius inserted since the last clear
+}
+
+impl SpatialHash {
+    /// An arena or cell size that makes no sense (zero, negative, not a
+    /// number) gets one cell covering everything, rather than none.
+    pub fn new(width: f32, height: f32, cell_size: f32) -> SpatialHash {
+        let count = |span: f32| {
+            let count = span / cell_size;
+            if count.is_finite() && count >= 1.0 { count as usize } else { 1 }
+        };
+        let size = |span: f32, count: usize| {
+            let size = span / count as f32;
+            if size.is_finite() && size > 0.0 { size } else { 1.0 }
+        };
+        let (columns, rows) = (count(width), count(height));
+        SpatialHash {
+            columns,
+            rows,
+            cell: Vec2::new(size(width, columns), size(height, rows)),
+            cells: vec![Vec::new(); columns * rows],
+            reach: 0.0,
+        }
+    }
+
+    /// Empty every cell, keeping the memory for the next tick.
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.reach = 0.0;
+    }
+
+    pub fn insert(&mut self, index: usize, position: Vec2, radius: f32) {
+        let cell = self.covered(position, 0.0).next().unwrap_or(0);
+        self.cells[cell].push(index);
+        self.reach = self.reach.max(radius);
+    }
+
+    /// Everything filed whose circle might touch this one, each index once.
+    /// These are candidates only; the exact test is up to the caller.
+    pub fn query(&self, position: Vec2, radius: f32, found: &mut Vec<usize>) {
+        found.clear();
+        for cell in self.covered(position, radius + self.reach) {
+            found.extend(&self.cells[cell]);
+        }
+    }
+
+    /// Cells under the circle's bounding box, wrapped onto the grid. A box
+    /// wider than the arena just covers every column once.
+    fn covered(&self, position: Vec2, radius: f32) -> impl Iterator<Item = usize> {
+        let span = move |centre: f32, size: f32, count: usize| {
+            let count = count.max(1) as i64;
+            let first = ((centre - radius) / size).floor() as i64;
+            let last = (((centre + radius) / size).floor() as i64).min(first.saturating_add(count - 1));
+            (first..=last).map(move |i| i.rem_euclid(count) as usize)
+        };
+        let columns = self.columns;
+        let xs = span(position.x, self.cell.x, self.columns);
+        span(position.y, self.cell.y, self.rows)
+            .flat_map(move |row| xs.clone().map(move |column| row * columns + column))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_neighbours_and_skips_the_far_side() {
+        let mut grid = SpatialHash::new(800.0, 600.0, 100.0);
+        grid.insert(0, Vec2::new(150.0, 150.0), 20.0);
+        grid.insert(1, Vec2::new(550.0, 450.0), 20.0);
+        let mut found = Vec::new();
+        grid.query(Vec2::new(170.0, 140.0), 10.0, &mut found);
+        assert_eq!(found, vec![0]);
+    }
+
+    #[test]
+    fn queries_wrap_around_the_edges() {
+        let mut grid = SpatialHash::new(800.0, 600.0, 100.0);
+        grid.insert(0, Vec2::new(795.0, 595.0), 10.0);
+        let mut found = Vec::new();
+        grid.query(Vec2::new(5.0, 5.0), 10.0, &mut found);
+        assert_eq!(found, vec![0]);
+    }
+
+    #[test]
+    fn big_queries_see_each_index_once() {
+        let mut grid = SpatialHash::new(800.0, 600.0, 100.0);
+        for i in 0..10 {
+            grid.insert(i, Vec2::new(i as f32 * 80.0, i as f32 * 60.0), 5.0);
+        }
+        let mut found = Vec::new();
+        grid.query(Vec2::new(400.0, 300.0), 2000.0, &mut found);
+        found.sort();
+        assert_eq!(found, (0..10).collect::<Vec<_>>());
+
+        grid.clear();
+        grid.query(Vec2::new(400.0, 300.0), 2000.0, &mut found);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn degenerate_sizes_still_make_a_grid() {
+        let mut found = Vec::new();
+        for (width, height, cell) in [(0.0, 0.0, 100.0), (800.0, 600.0, 0.0), (-5.0, f32::NAN, 100.0)] {
+            let mut grid = SpatialHash::new(width, height, cell);
+            grid.insert(0, Vec2::new(10.0, 10.0), 5.0);
+            grid.query(Vec2::new(f32::MAX, 10.0), 5.0, &mut found);
+            assert_eq!(found, vec![0]);
+        }
+
+        // Nor does a query from far off run the cell arithmetic over
+        let mut grid = SpatialHash::new(800.0, 600.0, 100.0);
+        grid.insert(0, Vec2::new(10.0, 10.0), 5.0);
+        grid.query(Vec2::splat(f32::MAX), 5.0, &mut found);
+        assert!(found.len() <= 1);
+    }
+}
@@ -0,0 +1,116 @@
+// Spielzustände und Menüs (Game states and menus)
+// ἀρχὴ καὶ τέλος
+
+use crate::input::Action;
+
+/// Where the game is, screen-wise. The `World` only knows about lives and
+/// respawn timers; this is the player-facing flow around it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameState {
+    Title,
+    Playing,
+    Paused,
+    Respawning,
+    GameOver,
+    HighScoreEntry,
+}
+
+impl GameState {
+    /// States in which the simulation keeps running.
+    pub fn simulates(&self) -> bool {
+        !matches!(self, GameState::Paused)
+    }
+
+    /// States in which the ship takes flight controls.
+    pub fn in_play(&self) -> bool {
+        matches!(self, GameState::Playing | GameState::Respawning)
+    }
+
+    /// The screens after the last life, which a key still held from
+    /// playing must not skip past.
+    pub fn after_game(&self) -> bool {
+        matches!(self, GameState::GameOver | GameState::HighScoreEntry)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseItem {
+    Resume,
+    Restart,
+    Options,
+    Quit,
+}
+
+impl PauseItem {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseItem::Resume => "RESUME",
+            PauseItem::Restart => "RESTART",
+            PauseItem::Options => "OPTIONS",
+            PauseItem::Quit => "QUIT",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionItem {
+    DebugInfo,
+    Controls,
+    Sound,
+    Volume, // left and right to change
+    Back,
+}
+
+/// A vertical list with one highlighted entry; up and down wrap around.
+pub struct Menu<T: Copy> {
+    pub items: Vec<T>,
+    pub selected: usize,
+}
+
+impl<T: Copy> Menu<T> {
+    pub fn new(items: Vec<T>) -> Menu<T> {
+        Menu { items, selected: 0 }
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+
+    pub fn current(&self) -> T {
+        self.items[self.selected]
+    }
+}
+
+impl Menu<PauseItem> {
+    pub fn pause() -> Menu<PauseItem> {
+        Menu::new(vec![
+            PauseItem::Resume,
+            PauseItem::Restart,
+            PauseItem::Options,
+            PauseItem::Quit,
+        ])
+    }
+}
+
+impl Menu<OptionItem> {
+    pub fn options() -> Menu<OptionItem> {
+        Menu::new(vec![
+            OptionItem::DebugInfo,
+            OptionItem::Controls,
+            OptionItem::Sound,
+            OptionItem::Volume,
+            OptionItem::Back,
+        ])
+    }
+}
+
+impl Menu<Action> {
+    /// Every action, to pick one to rebind.
+    pub fn controls() -> Menu<Action> {
+        Menu::new(Action::ALL.to_vec())
+    }
+}
@@ -0,0 +1,1110 @@
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f32::consts::PI;
+
+use crate::asteroid::{Asteroid, AsteroidSize, Impact};
+use crate::audio::Sound;
+use crate::bullet::{Bullet, BULLET_MASS};
+use crate::collision;
+use crate::config::Config;
+use crate::input::{Action, InputEvent};
+use crate::particle::{Emitter, ParticleSystem};
+use crate::saucer::{Saucer, SaucerSize};
+use crate::ship::Ship;
+use crate::spatial::SpatialHash;
+
+/// Simulation rate; every entity update runs with this fixed `dt`.
+pub const TICK_RATE: f32 = 120.0;
+pub const TICK_DT: f32 = 1.0 / TICK_RATE;
+
+// Wellen (Waves): each cleared field brings a bigger, faster one
+const FIRST_WAVE_ASTEROIDS: usize = 8;
+const EXTRA_ASTEROIDS_PER_WAVE: usize = 2;
+const MAX_WAVE_ASTEROIDS: usize = 16;
+const SPEEDUP_PER_WAVE: f32 = 0.1;
+pub const WAVE_BANNER_TIME: f32 = 2.0;
+
+/// Sounds kept for the shell to play; far more than a busy tick makes.
+const MAX_QUEUED_SOUNDS: usize = 32;
+
+/// Mixed into the seed for the effects' own random stream, so that it
+/// doesn't just replay the gameplay stream's numbers.
+const PARTICLE_SEED: u64 = 0x5EED_F0A7_C1E5_0000;
+
+/// Ships at the start of a game, unless the config caps lives lower.
+const STARTING_LIVES: i32 = 5;
+
+/// How long the lives column flashes after an extra life.
+pub const EXTRA_LIFE_FLASH_TIME: f32 = 2.0;
+
+// Hyperraum (Hyperspace): re-entry may go wrong, more so in a crowd
+const HYPERSPACE_BASE_RISK: f32 = 0.05;
+const HYPERSPACE_RISK_PER_ASTEROID: f32 = 0.08;
+const HYPERSPACE_MAX_RISK: f32 = 0.6;
+const HYPERSPACE_CROWD_RADIUS: f32 = 250.0;
+
+// Untertassen (Saucers): one at a time, every so often
+const SAUCER_DELAY_MIN: f32 = 10.0;
+const SAUCER_DELAY_MAX: f32 = 20.0;
+const SMALL_SAUCER_SCORE: f32 = 20_000.0; // small saucers get likelier up to this score
+
+/// Broad-phase cell size, a few small fragments across.
+const GRID_CELL: f32 = 80.0;
+
+/// Bildschirmgrenzen Wrapping (Screen edge wrapping) for anything that flies.
+/// `previous` is moved along with the jump so that interpolating between the
+/// last two ticks doesn't smear the object across the whole screen.
+pub fn wrap_position(position: &mut Vec2, previous: &mut Vec2, width: f32, height: f32) {
+    let before = *position;
+    if position.x < 0.0 {
+        position.x = width;
+    } else if position.x > width {
+        position.x = 0.0;
+    }
+    if position.y < 0.0 {
+        position.y = height;
+    } else if position.y > height {
+        position.y = 0.0;
+    }
+    *previous += *position - before;
+}
+
+/// Geisterbilder (Ghosts): the offsets to draw something of `radius` at
+/// `position` with are zero, then one across each edge it pokes out over
+/// and, over two, one across the corner. A `width` of zero draws no ghosts
+/// left and right, for saucers that only wrap vertically.
+pub fn ghost_offsets(position: Vec2, radius: f32, width: f32, height: f32) -> Vec<Vec2> {
+    let across = |at: f32, span: f32| {
+        if span <= 0.0 {
+            0.0
+        } else if at - radius < 0.0 {
+            span
+        } else if at + radius > span {
+            -span
+        } else {
+            0.0
+        }
+    };
+    let (dx, dy) = (across(position.x, width), across(position.y, height));
+    let mut offsets = vec![Vec2::ZERO];
+    if dx != 0.0 {
+        offsets.push(Vec2::new(dx, 0.0));
+    }
+    if dy != 0.0 {
+        offsets.push(Vec2::new(0.0, dy));
+    }
+    if dx != 0.0 && dy != 0.0 {
+        offsets.push(Vec2::new(dx, dy));
+    }
+    offsets
+}
+
+/// How far to move something at `to` so that it sits at its image nearest
+/// `from`, on the same side of any edge. `arena` is (width, height); see
+/// `collision::wrapped` for axes that don't wrap.
+fn wrap_shift(from: Vec2, to: Vec2, arena: Vec2) -> Vec2 {
+    from + collision::wrapped_delta(from, to, arena.x, arena.y) - to
+}
+
+/// The stretch a bullet covered in the last tick, as seen from a target
+/// that itself moved by `target_motion` meanwhile. Testing this instead of
+/// the bullet's end point means nothing gets skipped over.
+fn bullet_sweep(bullet: &Bullet, target_motion: Vec2) -> (Vec2, Vec2) {
+    (bullet.prev_position + target_motion, bullet.position)
+}
+
+/// Equal and opposite impulse between two asteroids touching at offsets
+/// `ra` and `rb` from their centres, along `normal` from `a` to `b`. Nothing
+/// happens if they are already moving apart there.
+fn bounce(a: &mut Asteroid, b: &mut Asteroid, ra: Vec2, rb: Vec2, normal: Vec2) {
+    let velocity_at = |asteroid: &Asteroid, r: Vec2| asteroid.velocity + r.perp() * asteroid.rotation_speed;
+    let closing = (velocity_at(b, rb) - velocity_at(a, ra)).dot(normal);
+    if closing >= 0.0 {
+        return;
+    }
+    let (mass_a, mass_b) = (a.mass(), b.mass());
+    let (inertia_a, inertia_b) = (a.moment_of_inertia(), b.moment_of_inertia());
+    let (lever_a, lever_b) = (ra.perp_dot(normal), rb.perp_dot(normal));
+    let impulse = -2.0 * closing
+        / (1.0 / mass_a + 1.0 / mass_b + lever_a * lever_a / inertia_a + lever_b * lever_b / inertia_b);
+
+    a.velocity -= normal * impulse / mass_a;
+    b.velocity += normal * impulse / mass_b;
+    a.rotation_speed -= lever_a * impulse / inertia_a;
+    b.rotation_speed += lever_b * impulse / inertia_b;
+}
+
+fn shifted(outline: &[Vec2], shift: Vec2) -> Vec<Vec2> {
+    outline.iter().map(|point| *point + shift).collect()
+}
+
+/// Where the bullet hit, if it did: the point on its path nearest the
+/// target's centre.
+fn swept_hit(bullet: &Bullet, target_position: Vec2, target_motion: Vec2, radius: f32, outline: &[Vec2], arena: Vec2) -> Option<Vec2> {
+    let shift = wrap_shift(target_position, bullet.position, arena);
+    let (start, end) = bullet_sweep(bullet, target_motion);
+    let (start, end) = (start + shift, end + shift);
+    let nearest = collision::closest_point_on_segment(target_position, start, end);
+    ((nearest - target_position).length() < radius && collision::segment_hits_polygon(start, end, outline))
+        .then_some(nearest)
+}
+
+/// Blend between the previous and current tick for drawing.
+pub fn interpolate_angle(previous: f32, current: f32, alpha: f32) -> f32 {
+    previous + (current - previous) * alpha
+}
+
+/// Something to play in: a minimised window reports nothing of the sort.
+pub fn playable_size(width: f32, height: f32) -> bool {
+    width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0
+}
+
+/// Debug lines kept for the shell to write out; a headless world that
+/// nobody drains drops the oldest.
+const MAX_QUEUED_LOG: usize = 64;
+
+// τὰ πάντα ῥεῖ
+/// The whole game without a window: advance it with `step(dt)` and read the
+/// entities back out for drawing.
+pub struct World {
+    pub width: f32,
+    pub height: f32,
+    pub tick: u64,
+    pub seed: u64,
+    rng: StdRng, // every random decision in the game comes from here
+    pub asteroids: Vec<Asteroid>,
+    pub ship: Ship,
+    pub bullets: Vec<Bullet>,
+    pub saucer: Option<Saucer>,
+    pub saucer_timer: f32, // until the next saucer shows up
+    pub enemy_bullets: Vec<Bullet>,
+    pub game_over: bool,
+    pub lives: i32,
+    pub thrust: bool, // keys held down; kept here, not on the ship, so they outlast it
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    pub firing: bool,
+    pub steer: f32, // the stick's turn as last reported, -1 to 1
+    pub extra_life_flash: f32, // seconds the lives column has left to flash
+    pub score: i32,
+    pub respawn_timer: f32,
+    pub wave: u32,
+    pub wave_banner: f32, // seconds the "WAVE N" banner has left; the wave spawns when it ends
+    pub particles: ParticleSystem,
+    pub sounds: Vec<Sound>, // to be played, oldest first; whoever plays them drains this
+    pub score_popup: Option<(String, Vec2, f32)>, // text, position, lifetime
+    pub debug_info: String, // the latest debug line
+    pub log: Vec<String>, // debug lines not yet written out, oldest first; the shell drains this
+    pub config: Config, // the rules this game runs by; fixed for its whole length
+    grid: SpatialHash, // asteroids by position, refilled whenever they move or break
+    outlines: Vec<(Vec<Vec2>, f32)>, // each asteroid's outline and bounding radius, matching `grid`
+}
+
+// Kollisionserkennung und Spiellogik
+// ἀνάγκη δ᾽οὐδὲ θεοὶ μάχονται
+impl World {
+    /// Same seed, arena, config and inputs give the same game.
+    pub fn new(width: f32, height: f32, seed: u64, config: Config) -> World {
+        World::with_rng(width, height, seed, config, StdRng::seed_from_u64(seed))
+    }
+
+    /// Asteroids drifting across an empty field, for behind the title screen.
+    pub fn attract(width: f32, height: f32, seed: u64, config: Config) -> World {
+        let mut world = World::new(width, height, seed, config);
+        world.lives = 0;
+        world.game_over = true;
+        world
+    }
+
+    fn with_rng(width: f32, height: f32, seed: u64, config: Config, rng: StdRng) -> World {
+        let mut ship = Ship::new();
+        ship.reset_position(width, height);
+
+        let mut world = World {
+            width,
+            height,
+            tick: 0,
+            seed,
+            rng,
+            asteroids: Vec::new(),
+            ship,
+            bullets: Vec::new(),
+            saucer: None,
+            saucer_timer: 0.0,
+            enemy_bullets: Vec::new(),
+            game_over: false,
+            lives: STARTING_LIVES.min(config.max_lives),
+            thrust: false,
+            rotate_left: false,
+            rotate_right: false,
+            firing: false,
+            steer: 0.0,
+            extra_life_flash: 0.0,
+            score: 0,
+            respawn_timer: 0.0,
+            wave: 1,
+            wave_banner: WAVE_BANNER_TIME,
+            particles: ParticleSystem::new(seed ^ PARTICLE_SEED),
+            sounds: Vec::new(),
+            score_popup: None,
+            debug_info: String::new(),
+            log: Vec::new(),
+            config,
+            grid: SpatialHash::new(width, height, GRID_CELL),
+            outlines: Vec::new(),
+        };
+        world.spawn_wave();
+        world.saucer_timer = world.rng.gen_range(SAUCER_DELAY_MIN..SAUCER_DELAY_MAX);
+        world
+    }
+
+    fn spawn_wave(&mut self) {
+        let extra = (self.wave as usize - 1) * EXTRA_ASTEROIDS_PER_WAVE;
+        let count = (FIRST_WAVE_ASTEROIDS + extra).min(MAX_WAVE_ASTEROIDS);
+        let speed_scale = 1.0 + (self.wave - 1) as f32 * SPEEDUP_PER_WAVE;
+        for _ in 0..count {
+            let asteroid = Asteroid::new_with_size(&mut self.rng, self.width, self.height, AsteroidSize::Large, speed_scale);
+            self.asteroids.push(asteroid);
+        }
+    }
+
+    /// Follows the window when it is resized; wrapping uses the new bounds
+    /// from the next step on. A size with no room to play in is ignored.
+    pub fn set_arena_size(&mut self, width: f32, height: f32) {
+        if !playable_size(width, height) {
+            return;
+        }
+        self.width = width;
+        self.height = height;
+        self.grid = SpatialHash::new(width, height, GRID_CELL);
+        self.index_asteroids();
+    }
+
+    // πάντα χωρεῖ καὶ οὐδὲν μένει
+    /// Advance the simulation by one tick. Callers should always pass
+    /// `TICK_DT`; drag and decay are per second, but collisions are only
+    /// as good as the step is small.
+    pub fn step(&mut self, dt: f32) {
+        let (width, height) = (self.width, self.height);
+        self.tick += 1;
+
+        // Update score popup
+        if let Some((_, _, ref mut lifetime)) = self.score_popup {
+            *lifetime -= dt;
+            if *lifetime <= 0.0 {
+                self.score_popup = None;
+            }
+        }
+
+        if self.extra_life_flash > 0.0 {
+            self.extra_life_flash -= dt;
+        }
+
+        // Update particles
+        self.particles.update(dt);
+
+        // Only update ship if not game over
+        if !self.game_over {
+            if self.respawn_timer > 0.0 {
+                self.respawn_timer -= dt;
+                if self.respawn_timer <= 0.0 {
+                    let mut new_ship = Ship::new();
+                    new_ship.reset_position(width, height);
+                    self.ship = new_ship;
+                }
+            } else {
+                let was_in_hyperspace = self.ship.in_hyperspace();
+                self.ship.update(dt, width, height, self.config.turn, self.turning(), self.thrust);
+                if was_in_hyperspace && !self.ship.in_hyperspace() {
+                    self.reenter_from_hyperspace();
+                }
+                if self.config.fire.auto && self.firing {
+                    self.fire();
+                }
+                if self.thrust && !self.ship.in_hyperspace() {
+                    let heading = self.ship.rotation + PI;
+                    self.particles.emit(Emitter::ThrustExhaust, self.ship.nozzle(), self.ship.velocity, heading, 1.0);
+                }
+            }
+        }
+
+        // Neue Welle (Next wave) once the field is clear
+        if self.asteroids.is_empty() && self.wave_banner <= 0.0 && !self.game_over {
+            self.wave += 1;
+            self.wave_banner = WAVE_BANNER_TIME;
+            self.log_debug(&format!("Field cleared. Wave {} incoming", self.wave));
+        }
+        if self.wave_banner > 0.0 {
+            self.wave_banner -= dt;
+            if self.wave_banner <= 0.0 && self.asteroids.is_empty() {
+                self.spawn_wave();
+            }
+        }
+
+        // Always update asteroids and bullets
+        for asteroid in &mut self.asteroids {
+            asteroid.update(dt, width, height);
+        }
+        // Bouncing only changes velocities, so this holds until something breaks
+        self.index_asteroids();
+        if self.config.asteroid_collisions {
+            self.bounce_asteroids();
+        }
+
+        self.bullets.retain(|bullet| bullet.lifetime > 0.0);
+        for bullet in &mut self.bullets {
+            bullet.update(dt, width, height);
+        }
+
+        self.update_saucer(dt);
+        self.enemy_bullets.retain(|bullet| bullet.lifetime > 0.0);
+        for bullet in &mut self.enemy_bullets {
+            bullet.update(dt, width, height);
+        }
+
+        self.check_collisions();
+        self.check_saucer_collisions();
+    }
+
+    // ξένος
+    fn update_saucer(&mut self, dt: f32) {
+        let target = (self.ship_active() && !self.ship.in_hyperspace()).then_some(self.ship.position);
+
+        if let Some(saucer) = &mut self.saucer {
+            if !saucer.update(&mut self.rng, dt, self.width, self.height) {
+                self.saucer = None;
+                return;
+            }
+            if let Some(bullet) = saucer.fire(&mut self.rng, dt, target, self.score) {
+                self.enemy_bullets.push(bullet);
+            }
+            return;
+        }
+
+        if self.game_over {
+            return;
+        }
+        self.saucer_timer -= dt;
+        if self.saucer_timer <= 0.0 {
+            self.saucer_timer = self.rng.gen_range(SAUCER_DELAY_MIN..SAUCER_DELAY_MAX);
+            let small_chance = (self.score as f32 / SMALL_SAUCER_SCORE).clamp(0.1, 0.8);
+            let size = if self.rng.gen_bool(small_chance as f64) {
+                SaucerSize::Small
+            } else {
+                SaucerSize::Large
+            };
+            self.saucer = Some(Saucer::new(&mut self.rng, self.width, self.height, size));
+            self.log_debug(&format!("{:?} saucer incoming", size));
+        }
+    }
+
+    /// The ship is on screen and under the player's control.
+    pub fn ship_active(&self) -> bool {
+        !self.game_over && self.respawn_timer <= 0.0
+    }
+
+    /// Which way the player is turning, -1 to 1: a key turns all the way,
+    /// the stick as far as it's pushed; both keys at once cancel out.
+    fn turning(&self) -> f32 {
+        (self.rotate_right as i32 - self.rotate_left as i32) as f32 + self.steer
+    }
+
+    /// Shoot, unless the gun is still cooling down or the player already
+    /// has as many bullets in flight as the rules allow.
+    pub fn fire(&mut self) {
+        let rule = self.config.fire;
+        let ready = self.ship_active() && !self.ship.in_hyperspace() && self.ship.fire_timer <= 0.0;
+        if !ready || self.bullets.len() >= rule.max_bullets {
+            return;
+        }
+        self.ship.fire_timer = rule.cooldown;
+        self.bullets.push(self.ship.shoot());
+        self.play_sound(Sound::Fire);
+    }
+
+    /// Jump to a random spot. Whether the ship survives is decided on
+    /// re-entry, by how many asteroids are around it then.
+    pub fn hyperspace(&mut self) {
+        if !self.ship_active() || self.ship.in_hyperspace() {
+            return;
+        }
+        let destination = Vec2::new(
+            self.rng.gen_range(0.0..self.width),
+            self.rng.gen_range(0.0..self.height),
+        );
+        self.ship.jump_to(destination);
+        self.log_debug("Hyperspace!");
+    }
+
+    fn reenter_from_hyperspace(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let nearby = self.asteroids.iter()
+            .filter(|asteroid| {
+                collision::wrapped_delta(self.ship.position, asteroid.position, width, height).length()
+                    < HYPERSPACE_CROWD_RADIUS
+            })
+            .count();
+        let risk = (HYPERSPACE_BASE_RISK + nearby as f32 * HYPERSPACE_RISK_PER_ASTEROID).min(HYPERSPACE_MAX_RISK);
+        if self.rng.gen_bool(risk as f64) {
+            self.log_debug(&format!("Hyperspace re-entry failed ({} asteroids nearby)", nearby));
+            self.destroy_ship();
+        }
+    }
+
+    // Steuerungsbefehle (Control commands)
+    /// Apply one player input before the next step. Everything the player
+    /// does goes through here so that it can be recorded and replayed.
+    pub fn handle_input(&mut self, event: InputEvent) {
+        // Where the stick points, like the held keys, holds through
+        // hyperspace and respawns
+        if let InputEvent::Turn(amount) = event {
+            self.steer = amount.clamp(-1.0, 1.0);
+            return;
+        }
+        // Keys still go up and down in hyperspace; `fire` and `hyperspace`
+        // won't act until the ship is back
+        match event {
+            InputEvent::Pressed(Action::Restart) => self.reset(),
+            InputEvent::Pressed(Action::RotateLeft) => self.rotate_left = true,
+            InputEvent::Pressed(Action::RotateRight) => self.rotate_right = true,
+            InputEvent::Pressed(Action::Thrust) => self.thrust = true,
+            InputEvent::Pressed(Action::Fire) => {
+                self.firing = true;
+                self.fire();
+            }
+            InputEvent::Pressed(Action::Hyperspace) => self.hyperspace(),
+            InputEvent::Released(Action::Thrust) => self.thrust = false,
+            InputEvent::Released(Action::Fire) => self.firing = false,
+            InputEvent::Released(Action::RotateLeft) => self.rotate_left = false,
+            InputEvent::Released(Action::RotateRight) => self.rotate_right = false,
+            _ => (),
+        }
+    }
+
+    fn check_collisions(&mut self) {
+        if self.game_over {
+            return;
+        }
+
+        let arena = Vec2::new(self.width, self.height);
+
+        // Überprüfe Schiff-Asteroid Kollisionen (Check ship-asteroid collisions)
+        if self.ship.vulnerable() {
+            let ship_outline = self.ship.outline();
+            let ship_radius = self.ship.bounding_radius();
+            let mut nearby = Vec::new();
+            self.grid.query(self.ship.position, ship_radius, &mut nearby);
+            for index in nearby {
+                let (asteroid, (outline, radius)) = (&self.asteroids[index], &self.outlines[index]);
+                // Across an edge if need be: move the ship to meet the asteroid
+                let shift = wrap_shift(asteroid.position, self.ship.position, arena);
+                let distance = (asteroid.position - self.ship.position - shift).length();
+                if distance < radius + ship_radius && collision::polygons_overlap(&shifted(&ship_outline, shift), outline) {
+                    self.destroy_ship();
+                    return;
+                }
+            }
+        }
+
+        // Treffer (Hits): the player scores for these
+        let hits = self.bullet_hits(&self.bullets);
+        for &(_, asteroid_idx, _) in &hits {
+            let asteroid = &self.asteroids[asteroid_idx];
+            self.award(asteroid.size.points(), asteroid.position);
+        }
+        let mut spent: Vec<usize> = hits.iter().map(|(b, ..)| *b).collect();
+        spent.sort_unstable_by(|a, b| b.cmp(a));
+        for bullet_idx in spent {
+            self.bullets.swap_remove(bullet_idx);
+        }
+        self.break_asteroids(hits.into_iter().map(|(_, a, impact)| (a, impact)).collect());
+    }
+
+    /// Elastische Stöße (Elastic collisions): asteroids that touch bounce
+    /// apart as solid bodies, keeping momentum, angular momentum and energy.
+    fn bounce_asteroids(&mut self) {
+        let outlines = std::mem::take(&mut self.outlines);
+        let arena = Vec2::new(self.width, self.height);
+        let mut nearby = Vec::new();
+        for i in 0..self.asteroids.len() {
+            let (outline, radius) = &outlines[i];
+            let centre = self.asteroids[i].position;
+            self.grid.query(centre, *radius, &mut nearby);
+            for &j in nearby.iter().filter(|&&j| j > i) {
+                let (other_outline, other_radius) = &outlines[j];
+                let shift = wrap_shift(centre, self.asteroids[j].position, arena);
+                let other_centre = self.asteroids[j].position + shift;
+                if (other_centre - centre).length() >= radius + other_radius {
+                    continue;
+                }
+                let other_outline = shifted(other_outline, shift);
+                if !collision::polygons_overlap(outline, &other_outline) {
+                    continue;
+                }
+                // Deep inside each other there's no edge to push off; go
+                // straight apart instead
+                let (point, normal) = collision::contact(outline, &other_outline).unwrap_or((
+                    (centre + other_centre) / 2.0,
+                    (other_centre - centre).normalize_or_zero(),
+                ));
+                let (head, tail) = self.asteroids.split_at_mut(j);
+                bounce(&mut head[i], &mut tail[0], point - centre, point - other_centre, normal);
+            }
+        }
+        self.outlines = outlines;
+    }
+
+    /// Work out the outlines and bounding radii of the asteroids as they
+    /// are now, and refill the broad-phase grid to match.
+    fn index_asteroids(&mut self) {
+        self.outlines.clear();
+        self.outlines.extend(self.asteroids.iter().map(|asteroid| (asteroid.outline(), asteroid.bounding_radius())));
+        self.grid.clear();
+        for (index, (asteroid, (_, radius))) in self.asteroids.iter().zip(&self.outlines).enumerate() {
+            self.grid.insert(index, asteroid.position, *radius);
+        }
+    }
+
+    /// Pair up bullets with the asteroids they hit this tick, as (bullet,
+    /// asteroid) indices and how it hit. A bullet is spent on its first hit
+    /// and an asteroid only breaks once, however many bullets find it.
+    fn bullet_hits(&self, bullets: &[Bullet]) -> Vec<(usize, usize, Impact)> {
+        let arena = Vec2::new(self.width, self.height);
+        // The sweep starts where the bullet was, carried along by however
+        // far the target moved, so look that much further afield
+        let drift = self.asteroids.iter()
+            .map(|asteroid| (asteroid.position - asteroid.prev_position).length())
+            .fold(0.0, f32::max);
+
+        let mut broken = vec![false; self.asteroids.len()];
+        let mut hits = Vec::new();
+        let mut nearby = Vec::new();
+        for (bullet_idx, bullet) in bullets.iter().enumerate() {
+            let reach = (bullet.position - bullet.prev_position).length() + drift;
+            self.grid.query(bullet.position, reach, &mut nearby);
+            let hit = nearby.iter().copied().filter(|&index| !broken[index]).find_map(|index| {
+                let (asteroid, (outline, radius)) = (&self.asteroids[index], &self.outlines[index]);
+                let motion = asteroid.position - asteroid.prev_position;
+                swept_hit(bullet, asteroid.position, motion, *radius, outline, arena).map(|point| (index, point))
+            });
+            if let Some((asteroid_idx, point)) = hit {
+                broken[asteroid_idx] = true;
+                hits.push((bullet_idx, asteroid_idx, Impact {
+                    point,
+                    velocity: bullet.velocity,
+                    mass: BULLET_MASS,
+                }));
+            }
+        }
+        hits
+    }
+
+    /// Saucers and their bullets against everything else. Enemy fire and
+    /// saucer crashes break asteroids but only the player scores.
+    fn check_saucer_collisions(&mut self) {
+        if self.game_over {
+            return;
+        }
+
+        let arena = Vec2::new(self.width, self.height);
+
+        // Enemy bullets against the ship
+        if self.ship_active() && self.ship.vulnerable() {
+            let ship = &self.ship;
+            let (outline, radius) = (ship.outline(), ship.bounding_radius());
+            let motion = ship.position - ship.prev_position;
+            if let Some(hit) = self.enemy_bullets.iter()
+                .position(|bullet| swept_hit(bullet, ship.position, motion, radius, &outline, arena).is_some())
+            {
+                self.enemy_bullets.swap_remove(hit);
+                self.destroy_ship();
+                return;
+            }
+        }
+
+        // Enemy bullets against asteroids
+        let hits = self.bullet_hits(&self.enemy_bullets);
+        let mut spent: Vec<usize> = hits.iter().map(|(b, ..)| *b).collect();
+        spent.sort_unstable_by(|a, b| b.cmp(a));
+        for bullet_idx in spent {
+            self.enemy_bullets.swap_remove(bullet_idx);
+        }
+        self.break_asteroids(hits.into_iter().map(|(_, a, impact)| (a, impact)).collect());
+
+        let Some(saucer) = &self.saucer else {
+            return;
+        };
+        let (position, points) = (saucer.position, saucer.size.points());
+        let ramming = Impact {
+            point: position,
+            velocity: saucer.velocity,
+            mass: saucer.size.mass(),
+        };
+        let saucer_outline = saucer.outline();
+        let saucer_motion = saucer.position - saucer.prev_position;
+        let saucer_radius = saucer.size.radius();
+        // The saucer leaves for good off the sides; only top and bottom wrap
+        let saucer_arena = Vec2::new(0.0, self.height);
+
+        // Player bullets against the saucer
+        if let Some(hit) = self.bullets.iter()
+            .position(|bullet| swept_hit(bullet, position, saucer_motion, saucer_radius, &saucer_outline, saucer_arena).is_some())
+        {
+            self.bullets.swap_remove(hit);
+            self.award(points, position);
+            self.destroy_saucer();
+            return;
+        }
+
+        // Ramming it counts, but costs the ship
+        let ship_shift = wrap_shift(position, self.ship.position, saucer_arena);
+        if self.ship_active() && self.ship.vulnerable()
+            && collision::polygons_overlap(&shifted(&self.ship.outline(), ship_shift), &saucer_outline)
+        {
+            self.award(points, position);
+            self.destroy_saucer();
+            self.destroy_ship();
+            return;
+        }
+
+        // Flying into an asteroid; the lowest index wins, whatever order the grid has them in
+        let mut nearby = Vec::new();
+        self.grid.query(position, saucer_radius, &mut nearby);
+        nearby.sort_unstable();
+        let crash = nearby.into_iter().find_map(|i| {
+            let (asteroid, (outline, radius)) = (&self.asteroids[i], &self.outlines[i]);
+            let shift = wrap_shift(position, asteroid.position, saucer_arena);
+            let distance = (asteroid.position + shift - position).length();
+            (distance < radius + saucer_radius && collision::polygons_overlap(&shifted(outline, shift), &saucer_outline))
+                .then_some((i, shift))
+        });
+        if let Some((hit, shift)) = crash {
+            // Seen from the asteroid, on its side of any edge
+            let ramming = Impact { point: ramming.point - shift, ..ramming };
+            self.destroy_saucer();
+            self.break_asteroids(vec![(hit, ramming)]);
+        }
+    }
+
+    /// Add points to the score with a popup where they were earned.
+    fn award(&mut self, points: i32, position: Vec2) {
+        let before = self.score;
+        self.score += points;
+        self.score_popup = Some((
+            format!("+{}", points),
+            position,
+            1.0,
+        ));
+
+        // Freileben (Extra lives): one for every threshold crossed
+        let every = self.config.extra_life_every;
+        if every > 0 {
+            for _ in before / every..self.score / every {
+                self.extra_life();
+            }
+        }
+    }
+
+    fn extra_life(&mut self) {
+        if self.lives >= self.config.max_lives {
+            self.log_debug(&format!("Extra life at {} points, but already at the maximum of {}", self.score, self.lives));
+            return;
+        }
+        self.lives += 1;
+        self.extra_life_flash = EXTRA_LIFE_FLASH_TIME;
+        self.log_debug(&format!("Extra life at {} points. Lives: {}", self.score, self.lives));
+    }
+
+    // ἐκ τοῦ χάους
+    fn destroy_ship(&mut self) {
+        self.particles.emit(Emitter::ShipExplosion, self.ship.position, self.ship.velocity * 0.5, 0.0, 0.5);
+        self.particles.shatter(Emitter::ShipDebris, &self.ship.outline(), self.ship.velocity * 0.5);
+        self.play_sound(Sound::ShipExplosion);
+        self.lives -= 1;
+        self.log_debug(&format!("Ship destroyed. Lives remaining: {}", self.lives));
+
+        if self.lives <= 0 {
+            self.game_over = true;
+            self.log_debug(&format!("Game Over. Final score: {}", self.score));
+        } else {
+            self.respawn_timer = 2.0;
+            let mut new_ship = Ship::new();
+            new_ship.reset_position(self.width, self.height);
+            self.ship = new_ship;
+        }
+    }
+
+    fn destroy_saucer(&mut self) {
+        if let Some(saucer) = self.saucer.take() {
+            let scale = saucer.size.radius() / 20.0;
+            self.particles.emit(Emitter::ShipExplosion, saucer.position, saucer.velocity * 0.5, 0.0, scale);
+            self.play_sound(Sound::BangLarge);
+            self.log_debug(&format!("{:?} saucer destroyed", saucer.size));
+        }
+    }
+
+    /// Split or shatter the asteroids hit, by index, without scoring them.
+    fn break_asteroids(&mut self, mut hits: Vec<(usize, Impact)>) {
+        let broke = !hits.is_empty();
+        hits.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+        for (index, impact) in hits {
+            let asteroid = self.asteroids.swap_remove(index);
+            let scale = asteroid.size.radius() / 20.0;
+            self.particles.emit(Emitter::AsteroidDebris, asteroid.position, asteroid.velocity, 0.0, scale);
+            self.play_sound(Sound::bang(asteroid.size));
+            let back = -impact.velocity;
+            self.particles.emit(Emitter::ImpactSparks, impact.point, asteroid.velocity, back.y.atan2(back.x), 1.0);
+            let fragments = self.config.split_rule(asteroid.size)
+                .and_then(|rule| asteroid.split(&mut self.rng, &impact, rule));
+            if let Some(fragments) = fragments {
+                self.asteroids.extend(fragments);
+            }
+            self.log_debug(&format!("Asteroid destroyed. Remaining: {}", self.asteroids.len()));
+        }
+        if broke {
+            self.index_asteroids();
+        }
+    }
+
+    /// Start over, continuing the same random stream and tick count so that
+    /// a restart inside a recorded session stays reproducible.
+    pub fn reset(&mut self) {
+        let rng = std::mem::replace(&mut self.rng, StdRng::seed_from_u64(0));
+        let tick = self.tick;
+        let config = std::mem::take(&mut self.config);
+        let log = std::mem::take(&mut self.log);
+        let held = (self.thrust, self.rotate_left, self.rotate_right, self.firing, self.steer);
+        *self = World::with_rng(self.width, self.height, self.seed, config, rng);
+        self.tick = tick;
+        self.log = log;
+        (self.thrust, self.rotate_left, self.rotate_right, self.firing, self.steer) = held;
+    }
+
+    /// Ask for `sound`. A headless world that nobody drains forgets the
+    /// oldest; they'd be stale by the time anyone heard them anyway.
+    fn play_sound(&mut self, sound: Sound) {
+        if self.sounds.len() >= MAX_QUEUED_SOUNDS {
+            self.sounds.remove(0);
+        }
+        self.sounds.push(sound);
+    }
+
+    // γνῶσις
+    /// Note `msg` for the debug overlay and queue it for the log file.
+    /// The world keeps no clock and touches no files; the shell stamps
+    /// and writes out `log`.
+    pub fn log_debug(&mut self, msg: &str) {
+        self.debug_info = msg.to_string();
+        if self.log.len() >= MAX_QUEUED_LOG {
+            self.log.remove(0); // nobody is reading; keep only the latest
+        }
+        self.log.push(msg.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ship::FireRule;
+
+    /// A minute of play with some steering and shooting, for comparing.
+    fn play(seed: u64, config: Config) -> World {
+        let mut world = World::new(1280.0, 960.0, seed, config);
+        for tick in 0..(60.0 * TICK_RATE) as u64 {
+            match tick % 240 {
+                0 => world.handle_input(InputEvent::Pressed(Action::Thrust)),
+                30 => world.handle_input(InputEvent::Released(Action::Thrust)),
+                60 => world.handle_input(InputEvent::Pressed(Action::RotateLeft)),
+                100 => world.handle_input(InputEvent::Released(Action::RotateLeft)),
+                120 => world.handle_input(InputEvent::Pressed(Action::Fire)),
+                121 => world.handle_input(InputEvent::Released(Action::Fire)),
+                _ => {}
+            }
+            world.step(TICK_DT);
+        }
+        world
+    }
+
+    fn snapshot(world: &World) -> (u64, i32, i32, Vec2, Vec<Vec2>, usize) {
+        (
+            world.tick,
+            world.score,
+            world.lives,
+            world.ship.position,
+            world.asteroids.iter().map(|asteroid| asteroid.position).collect(),
+            world.particles.len(),
+        )
+    }
+
+    #[test]
+    fn same_seed_and_inputs_give_the_same_game() {
+        let config = Config { asteroid_collisions: true, ..Config::default() };
+        assert_eq!(snapshot(&play(42, config.clone())), snapshot(&play(42, config)));
+    }
+
+    #[test]
+    fn another_seed_gives_another_game() {
+        let first = World::new(1280.0, 960.0, 1, Config::default());
+        let second = World::new(1280.0, 960.0, 2, Config::default());
+        assert_ne!(snapshot(&first).4, snapshot(&second).4);
+    }
+
+    #[test]
+    fn the_stick_turns_by_as_much_as_its_pushed() {
+        let mut world = World::new(1280.0, 960.0, 3, Config::default());
+        let start = world.ship.rotation;
+        world.handle_input(InputEvent::Turn(0.5));
+        for _ in 0..TICK_RATE as usize {
+            world.step(TICK_DT);
+        }
+        let turned = world.ship.rotation - start;
+        assert!((turned - world.config.turn.speed * 0.5).abs() < 1e-3, "{}", turned);
+
+        // A held key on top still can't go past full speed
+        world.handle_input(InputEvent::Pressed(Action::RotateRight));
+        world.step(TICK_DT);
+        assert_eq!(world.ship.angular_velocity, world.config.turn.speed);
+    }
+
+    #[test]
+    fn starting_lives_respect_the_cap() {
+        let config = Config { max_lives: 3, ..Config::default() };
+        assert_eq!(World::new(1280.0, 960.0, 1, config).lives, 3);
+        assert_eq!(World::new(1280.0, 960.0, 1, Config::default()).lives, STARTING_LIVES);
+    }
+
+    /// Two medium asteroids already overlapping, closing in and spinning.
+    fn collision_course(config: Config) -> World {
+        let mut world = World::new(1280.0, 960.0, 12, config);
+        let mut rng = StdRng::seed_from_u64(12);
+        let mut a = Asteroid::new_with_size(&mut rng, 1280.0, 960.0, AsteroidSize::Medium, 1.0);
+        let mut b = Asteroid::new_with_size(&mut rng, 1280.0, 960.0, AsteroidSize::Medium, 1.0);
+        (a.position, a.velocity, a.rotation_speed) = (Vec2::new(600.0, 300.0), Vec2::new(60.0, 5.0), 1.0);
+        (b.position, b.velocity, b.rotation_speed) = (Vec2::new(650.0, 310.0), Vec2::new(-40.0, -10.0), -0.5);
+        world.asteroids = vec![a, b];
+        world
+    }
+
+    /// Linear momentum, and angular momentum about the origin.
+    fn momentum(world: &World) -> (Vec2, f32) {
+        world.asteroids.iter().fold((Vec2::ZERO, 0.0), |(linear, angular), asteroid| {
+            let p = asteroid.velocity * asteroid.mass();
+            (linear + p, angular + asteroid.moment_of_inertia() * asteroid.rotation_speed + asteroid.position.perp_dot(p))
+        })
+    }
+
+    #[test]
+    fn bouncing_asteroids_keep_their_momentum() {
+        let mut world = collision_course(Config { asteroid_collisions: true, ..Config::default() });
+        let velocities: Vec<Vec2> = world.asteroids.iter().map(|asteroid| asteroid.velocity).collect();
+        let (linear, angular) = momentum(&world);
+        world.step(TICK_DT);
+        assert_eq!(world.asteroids.len(), 2);
+        assert_ne!(world.asteroids[0].velocity, velocities[0], "they should have bounced");
+        let (after_linear, after_angular) = momentum(&world);
+        assert!((after_linear - linear).length() < 1e-3 * linear.length(), "{} vs {}", after_linear, linear);
+        assert!((after_angular - angular).abs() < 1e-3 * angular.abs(), "{} vs {}", after_angular, angular);
+    }
+
+    #[test]
+    fn asteroids_pass_through_each_other_by_default() {
+        let mut world = collision_course(Config::default());
+        let before: Vec<(Vec2, f32)> = world.asteroids.iter().map(|asteroid| (asteroid.velocity, asteroid.rotation_speed)).collect();
+        world.step(TICK_DT);
+        let after: Vec<(Vec2, f32)> = world.asteroids.iter().map(|asteroid| (asteroid.velocity, asteroid.rotation_speed)).collect();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn a_cleared_field_brings_a_bigger_wave() {
+        let mut world = World::new(1280.0, 960.0, 13, Config::default());
+        while world.wave_banner > 0.0 {
+            world.step(TICK_DT);
+        }
+        for wave in 2..=6 {
+            world.asteroids.clear();
+            world.step(TICK_DT);
+            assert_eq!(world.wave, wave);
+            assert!(world.asteroids.is_empty(), "not until the banner is done");
+            while world.wave_banner > 0.0 {
+                world.step(TICK_DT);
+            }
+            let count = (FIRST_WAVE_ASTEROIDS + (wave as usize - 1) * EXTRA_ASTEROIDS_PER_WAVE).min(MAX_WAVE_ASTEROIDS);
+            assert_eq!(world.asteroids.len(), count, "wave {}", wave);
+        }
+    }
+
+    #[test]
+    fn saucers_show_up_on_their_timer() {
+        let mut world = World::new(1280.0, 960.0, 14, Config::default());
+        let due = world.saucer_timer;
+        assert!((SAUCER_DELAY_MIN..SAUCER_DELAY_MAX).contains(&due));
+        let mut ticks = 0;
+        while world.saucer.is_none() && ticks <= (SAUCER_DELAY_MAX * TICK_RATE) as usize {
+            world.asteroids.clear(); // nothing to lose the ship or the saucer to
+            world.step(TICK_DT);
+            ticks += 1;
+        }
+        assert!(world.saucer.is_some());
+        assert!((ticks as f32 * TICK_DT - due).abs() <= TICK_DT, "after {} ticks, due at {}", ticks, due);
+        assert!((SAUCER_DELAY_MIN..SAUCER_DELAY_MAX).contains(&world.saucer_timer));
+
+        // Not behind the title screen, though
+        let mut attract = World::attract(1280.0, 960.0, 14, Config::default());
+        for _ in 0..(SAUCER_DELAY_MAX * TICK_RATE) as usize {
+            attract.step(TICK_DT);
+        }
+        assert!(attract.saucer.is_none());
+    }
+
+    #[test]
+    fn one_big_award_can_earn_two_lives() {
+        let config = Config { extra_life_every: 1000, max_lives: 10, ..Config::default() };
+        let mut world = World::new(1280.0, 960.0, 1, config);
+        world.award(900, Vec2::ZERO);
+        assert_eq!(world.lives, STARTING_LIVES);
+        world.award(1200, Vec2::ZERO);
+        assert_eq!(world.lives, STARTING_LIVES + 2);
+        assert!(world.extra_life_flash > 0.0);
+    }
+
+    #[test]
+    fn extra_lives_stop_at_the_cap() {
+        let config = Config { extra_life_every: 1000, max_lives: STARTING_LIVES + 1, ..Config::default() };
+        let mut world = World::new(1280.0, 960.0, 1, config);
+        world.award(5000, Vec2::ZERO);
+        assert_eq!(world.lives, STARTING_LIVES + 1);
+    }
+
+    #[test]
+    fn a_restart_starts_the_thresholds_over() {
+        let config = Config { extra_life_every: 1000, max_lives: 10, ..Config::default() };
+        let mut world = World::new(1280.0, 960.0, 1, config);
+        world.award(1500, Vec2::ZERO);
+        world.handle_input(InputEvent::Pressed(Action::Restart));
+        assert_eq!((world.score, world.lives), (0, STARTING_LIVES));
+        world.award(500, Vec2::ZERO);
+        assert_eq!(world.lives, STARTING_LIVES);
+        world.award(500, Vec2::ZERO);
+        assert_eq!(world.lives, STARTING_LIVES + 1);
+    }
+
+    #[test]
+    fn plays_without_a_window() {
+        let mut world = World::new(1280.0, 960.0, 5, Config::default());
+        world.asteroids.clear(); // nothing to crash into
+        let start = world.ship.position;
+        world.thrust = true;
+        world.fire();
+        assert_eq!(world.bullets.len(), 1);
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+        assert_ne!(world.ship.position, start);
+    }
+
+    #[test]
+    fn held_keys_outlast_hyperspace() {
+        let mut world = World::new(1280.0, 960.0, 2, Config::default());
+        world.asteroids.clear(); // nothing to make re-entry riskier
+        world.handle_input(InputEvent::Pressed(Action::Thrust));
+        world.handle_input(InputEvent::Pressed(Action::Hyperspace));
+        assert!(world.ship.in_hyperspace());
+        world.handle_input(InputEvent::Pressed(Action::Fire));
+        assert!(world.bullets.is_empty());
+        for _ in 0..TICK_RATE as usize {
+            world.step(TICK_DT);
+        }
+        assert!(world.ship_active() && !world.ship.in_hyperspace(), "lost the ship on re-entry");
+        assert!(world.thrust && world.firing);
+        assert!(world.ship.velocity.length() > 0.0);
+    }
+
+    #[test]
+    fn held_keys_outlast_the_ship() {
+        let mut world = World::new(1280.0, 960.0, 4, Config::default());
+        world.asteroids.clear();
+        world.handle_input(InputEvent::Pressed(Action::RotateLeft));
+        world.destroy_ship();
+        while !world.ship_active() {
+            world.step(TICK_DT);
+        }
+        let start = world.ship.rotation;
+        world.step(TICK_DT);
+        assert!(world.rotate_left);
+        assert!(world.ship.rotation < start);
+    }
+
+    #[test]
+    fn live_bullets_stop_at_the_cap() {
+        let config = Config { fire: FireRule { cooldown: 0.0, max_bullets: 3, auto: true }, ..Config::default() };
+        let mut world = World::new(1280.0, 960.0, 8, config);
+        world.asteroids.clear();
+        world.handle_input(InputEvent::Pressed(Action::Fire));
+        for _ in 0..30 {
+            world.step(TICK_DT);
+            assert!(world.bullets.len() <= 3);
+        }
+        assert_eq!(world.bullets.len(), 3);
+    }
+
+    #[test]
+    fn the_cooldown_spaces_out_shots() {
+        let config = Config { fire: FireRule { cooldown: 0.25, max_bullets: 100, auto: false }, ..Config::default() };
+        let mut world = World::new(1280.0, 960.0, 9, config);
+        world.asteroids.clear();
+        let mut shots = Vec::new();
+        for tick in 0..TICK_RATE as usize / 2 {
+            // Hammering the key is no faster than the gun allows
+            let before = world.bullets.len();
+            world.handle_input(InputEvent::Pressed(Action::Fire));
+            world.handle_input(InputEvent::Released(Action::Fire));
+            if world.bullets.len() > before {
+                shots.push(tick);
+            }
+            world.step(TICK_DT);
+        }
+        assert_eq!(shots, vec![0, 30]);
+    }
+
+    #[test]
+    fn keeps_its_arena_when_given_none() {
+        let mut world = World::new(1280.0, 960.0, 10, Config::default());
+        for (width, height) in [(0.0, 0.0), (800.0, -1.0), (f32::NAN, 600.0), (f32::INFINITY, 600.0)] {
+            world.set_arena_size(width, height);
+            assert_eq!((world.width, world.height), (1280.0, 960.0));
+        }
+        world.set_arena_size(800.0, 600.0);
+        for _ in 0..10 {
+            world.step(TICK_DT);
+        }
+        assert_eq!((world.width, world.height), (800.0, 600.0));
+    }
+
+    #[test]
+    fn sounds_nobody_plays_dont_pile_up() {
+        let config = Config { fire: FireRule { cooldown: 0.0, max_bullets: 100, auto: true }, ..Config::default() };
+        let mut world = World::new(1280.0, 960.0, 11, config);
+        world.asteroids.clear(); // only shots to hear
+        world.handle_input(InputEvent::Pressed(Action::Fire));
+        for _ in 0..100 {
+            world.step(TICK_DT);
+        }
+        assert_eq!(world.sounds.len(), MAX_QUEUED_SOUNDS);
+        assert_eq!(world.sounds.last(), Some(&Sound::Fire));
+    }
+
+    #[test]
+    fn debug_lines_wait_for_the_shell() {
+        let mut world = World::new(1280.0, 960.0, 7, Config::default());
+        world.log_debug("hello");
+        assert_eq!(world.debug_info, "hello");
+        assert_eq!(world.log, vec!["hello".to_string()]);
+        for i in 0..MAX_QUEUED_LOG * 2 {
+            world.log_debug(&i.to_string());
+        }
+        assert_eq!(world.log.len(), MAX_QUEUED_LOG);
+        assert_eq!(world.log.last().map(String::as_str), Some("127"));
+    }
+}